@@ -1,15 +1,43 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Ident, ItemTrait, TraitItem, LitStr, LitBool, Visibility};
+use std::time::Duration;
+use quote::ToTokens;
+use syn::{Ident, ItemTrait, TraitItem, LitStr, LitBool, Visibility, Meta};
 use syn::__private::{Span, TokenStream2};
 use syn::parse::Parser;
-use crate::method::MethodConfig;
+use syn::punctuated::Punctuated;
+use clientix_core::client::auth::api_key::ApiKeyLocation;
+use clientix_core::client::balancer::BalanceStrategy;
+use clientix_core::core::duration::parse_duration;
+use crate::method::{HeaderConfig, MethodConfig};
+use crate::utils::compile_interpolated;
+
+const HEADER_CLIENT_MACRO: &str = "header";
+const DEFAULT_API_KEY_HEADER: &str = "X-Api-Key";
+const DEFAULT_API_KEY_PARAMETER: &str = "api_key";
+
+#[derive(Clone, Default)]
+struct ApiKeyConfig {
+    location: ApiKeyLocation,
+    name: Option<String>,
+    value: Option<String>
+}
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct ClientConfig {
     item: Option<ItemTrait>,
     url: Option<String>,
+    urls: Vec<String>,
+    load_balancing: Option<BalanceStrategy>,
     path: Option<String>,
+    user_agent: Option<String>,
+    headers: Vec<HeaderConfig>,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    connection_verbose: Option<bool>,
+    api_key: Option<ApiKeyConfig>,
+    cookie_store: Option<bool>,
     async_supported: bool,
     methods: Vec<MethodConfig>
 }
@@ -17,7 +45,7 @@ pub struct ClientConfig {
 impl ClientConfig {
 
     pub fn create(item: TokenStream, attrs: TokenStream) -> Self {
-        let mut client_attrs = ClientConfig { item: None, url: None, path: None, async_supported: false, methods: vec![] };
+        let mut client_attrs = ClientConfig::default();
 
         client_attrs.parse(TokenStream2::from(item), TokenStream2::from(attrs));
 
@@ -50,25 +78,46 @@ impl ClientConfig {
     }
 
     fn compile_builder(&self) -> TokenStream2 {
-        let client_url = self.get_url();
-        let client_path = self.get_path();
+        let client_url = compile_interpolated(self.get_url().as_str());
+        let client_path = compile_interpolated(self.get_path().as_str());
         let client_struct_name = self.get_ident();
         let client_visibility = self.get_vis();
         let client_builder_name = Ident::new(&format!("{}{}", self.get_ident(), "Builder"), Span::call_site());
-        let client_type_method = if self.async_supported { quote! {asynchronous()} } else { quote! {blocking()} };
+        let client_type_method = if self.async_supported { quote! {try_asynchronous()} } else { quote! {try_blocking()} };
+        let compiled_defaults = self.compile_defaults();
+        let compiled_api_key = self.compile_api_key(quote!(value));
+        let client_coalesce = match self.async_supported {
+            true => quote! {
+                pub fn coalesce(mut self, vary_headers: &[&str]) -> Self {
+                    self.clientix_builder = self.clientix_builder.coalesce(vary_headers);
+                    self
+                }
+            },
+            false => TokenStream2::new()
+        };
 
         TokenStream2::from(quote! {
             #client_visibility struct #client_builder_name {
-                clientix_builder: clientix::client::ClientixBuilder
+                clientix_builder: clientix::client::ClientixBuilder,
+                interpolation_error: Option<clientix::client::response::ClientixError>
             }
 
             impl #client_builder_name {
+                #[allow(unused_mut)]
                 pub fn new() -> Self {
+                    let mut interpolation_error = None;
                     let clientix_builder = clientix::client::Clientix::builder()
                         .url(#client_url)
-                        .path(#client_path);
+                        .path(#client_path)
+                        #compiled_defaults;
+
+                    Self { clientix_builder, interpolation_error }
+                }
 
-                    Self { clientix_builder }
+                pub fn from_config(mut self, path: &str, name: &str) -> clientix::client::response::ClientixResult<Self> {
+                    let properties = clientix::client::properties::ClientixProperties::load(path, name)?;
+                    self.clientix_builder = self.clientix_builder.properties(properties)?;
+                    Ok(self)
                 }
 
                 pub fn url(mut self, url: &str) -> Self {
@@ -76,6 +125,38 @@ impl ClientConfig {
                     self
                 }
 
+                pub fn urls(mut self, urls: &[&str]) -> Self {
+                    self.clientix_builder = self.clientix_builder.urls(urls);
+                    self
+                }
+
+                pub fn load_balancing(mut self, strategy: clientix::client::balancer::BalanceStrategy) -> Self {
+                    self.clientix_builder = self.clientix_builder.load_balancing(strategy);
+                    self
+                }
+
+                pub fn endpoint_ejection(mut self, failures: u32, cool_down: std::time::Duration) -> Self {
+                    self.clientix_builder = self.clientix_builder.endpoint_ejection(failures, cool_down);
+                    self
+                }
+
+                pub fn circuit_breaker(mut self, config: clientix::client::breaker::CircuitBreakerConfig) -> Self {
+                    self.clientix_builder = self.clientix_builder.circuit_breaker(config);
+                    self
+                }
+
+                pub fn rate_limit(mut self, config: clientix::client::limiter::RateLimitConfig) -> Self {
+                    self.clientix_builder = self.clientix_builder.rate_limit(config);
+                    self
+                }
+
+                pub fn cache(mut self, storage: impl clientix::client::cache::ClientixCacheStorage + 'static) -> Self {
+                    self.clientix_builder = self.clientix_builder.cache(storage);
+                    self
+                }
+
+                #client_coalesce
+
                 pub fn path(mut self, path: &str) -> Self {
                     self.clientix_builder = self.clientix_builder.path(path);
                     self
@@ -100,6 +181,36 @@ impl ClientConfig {
                     self.clientix_builder = self.clientix_builder.bearer_auth(token);
                     self
                 }
+
+                pub fn digest_auth(mut self, username: &str, password: &str) -> Self {
+                    self.clientix_builder = self.clientix_builder.digest_auth(username, password);
+                    self
+                }
+
+                pub fn api_key(mut self, value: &str) -> Self {
+                    self.clientix_builder = self.clientix_builder #compiled_api_key;
+                    self
+                }
+
+                pub fn cookie_store(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.cookie_store(enabled);
+                    self
+                }
+
+                pub fn cookie_jar(mut self, cookie_jar: std::sync::Arc<clientix::client::cookie::ClientixCookieJar>) -> Self {
+                    self.clientix_builder = self.clientix_builder.cookie_jar(cookie_jar);
+                    self
+                }
+
+                pub fn auth_provider(mut self, auth_provider: impl clientix::client::auth::ClientixAuthProvider + 'static) -> Self {
+                    self.clientix_builder = self.clientix_builder.auth_provider(auth_provider);
+                    self
+                }
+                
+                pub fn signer(mut self, signer: impl clientix::client::signer::ClientixSigner + 'static) -> Self {
+                    self.clientix_builder = self.clientix_builder.signer(signer);
+                    self
+                }
                 
                 pub fn headers(mut self, headers: std::collections::HashMap<String, String>) -> Self {
                     self.clientix_builder = self.clientix_builder.headers(headers);
@@ -126,18 +237,213 @@ impl ClientConfig {
                     self
                 }
 
-                pub fn setup(self) -> #client_struct_name {
+                pub fn resolve(mut self, host: &str, addrs: &[std::net::SocketAddr]) -> Self {
+                    self.clientix_builder = self.clientix_builder.resolve(host, addrs);
+                    self
+                }
+
+                pub fn resolver(mut self, resolver: impl clientix::client::resolver::ClientixResolver + 'static) -> Self {
+                    self.clientix_builder = self.clientix_builder.resolver(resolver);
+                    self
+                }
+
+                #[cfg(unix)]
+                pub fn unix_socket(mut self, path: impl AsRef<std::path::Path>) -> Self {
+                    self.clientix_builder = self.clientix_builder.unix_socket(path);
+                    self
+                }
+
+                pub fn http1_only(mut self) -> Self {
+                    self.clientix_builder = self.clientix_builder.http1_only();
+                    self
+                }
+
+                pub fn http2_prior_knowledge(mut self) -> Self {
+                    self.clientix_builder = self.clientix_builder.http2_prior_knowledge();
+                    self
+                }
+
+                pub fn pool_idle_timeout(mut self, pool_idle_timeout: std::time::Duration) -> Self {
+                    self.clientix_builder = self.clientix_builder.pool_idle_timeout(pool_idle_timeout);
+                    self
+                }
+
+                pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+                    self.clientix_builder = self.clientix_builder.pool_max_idle_per_host(max);
+                    self
+                }
+
+                pub fn tcp_keepalive(mut self, interval: std::time::Duration) -> Self {
+                    self.clientix_builder = self.clientix_builder.tcp_keepalive(interval);
+                    self
+                }
+
+                pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.tcp_nodelay(enabled);
+                    self
+                }
+
+                pub fn local_address(mut self, address: std::net::IpAddr) -> Self {
+                    self.clientix_builder = self.clientix_builder.local_address(address);
+                    self
+                }
+
+                pub fn gzip(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.gzip(enabled);
+                    self
+                }
+
+                pub fn brotli(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.brotli(enabled);
+                    self
+                }
+
+                pub fn deflate(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.deflate(enabled);
+                    self
+                }
+
+                pub fn zstd(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.zstd(enabled);
+                    self
+                }
+
+                pub fn redirect(mut self, policy: clientix::client::redirect::RedirectPolicy) -> Self {
+                    self.clientix_builder = self.clientix_builder.redirect(policy);
+                    self
+                }
+
+                pub fn proxy(mut self, proxy: clientix::client::proxy::ClientixProxy) -> Self {
+                    self.clientix_builder = self.clientix_builder.proxy(proxy);
+                    self
+                }
+
+                pub fn no_proxy(mut self, hosts: &str) -> Self {
+                    self.clientix_builder = self.clientix_builder.no_proxy(hosts);
+                    self
+                }
+
+                pub fn env_proxy(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.env_proxy(enabled);
+                    self
+                }
+
+                pub fn tls_root_certificate(mut self, pem: &[u8]) -> Self {
+                    self.clientix_builder = self.clientix_builder.tls_root_certificate(pem);
+                    self
+                }
+
+                pub fn tls_built_in_roots(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.tls_built_in_roots(enabled);
+                    self
+                }
+
+                pub fn tls_identity(mut self, certificate_pem: &[u8], key_pem: &[u8]) -> Self {
+                    self.clientix_builder = self.clientix_builder.tls_identity(certificate_pem, key_pem);
+                    self
+                }
+
+                pub fn tls_pin(mut self, fingerprint: &str) -> Self {
+                    self.clientix_builder = self.clientix_builder.tls_pin(fingerprint);
+                    self
+                }
+
+                pub fn danger_accept_invalid_certs(mut self, enabled: bool) -> Self {
+                    self.clientix_builder = self.clientix_builder.danger_accept_invalid_certs(enabled);
+                    self
+                }
+
+                pub fn try_setup(self) -> clientix::client::response::ClientixResult<#client_struct_name> {
+                    if let Some(error) = self.interpolation_error {
+                        return Err(error);
+                    }
+
                     let clientix = self.clientix_builder.build();
 
-                    #client_struct_name {
-                        client: clientix.#client_type_method,
+                    Ok(#client_struct_name {
+                        client: clientix.#client_type_method?,
                         config: clientix.config().clone()
-                    }
+                    })
+                }
+
+                pub fn setup(self) -> #client_struct_name {
+                    self.try_setup().expect("failed to set up the client")
                 }
             }
         })
     }
 
+    fn compile_defaults(&self) -> TokenStream2 {
+        let mut stream = TokenStream2::new();
+
+        if !self.urls.is_empty() {
+            let urls = self.urls.iter().map(|url| compile_interpolated(url)).collect::<Vec<_>>();
+            stream.extend(quote!(.urls(&[#(#urls),*])));
+        }
+
+        if let Some(strategy) = self.load_balancing {
+            let strategy = match strategy {
+                BalanceStrategy::RoundRobin => quote!(RoundRobin),
+                BalanceStrategy::Random => quote!(Random),
+                BalanceStrategy::LeastOutstanding => quote!(LeastOutstanding)
+            };
+            stream.extend(quote!(.load_balancing(clientix::client::balancer::BalanceStrategy::#strategy)));
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent = compile_interpolated(user_agent);
+            stream.extend(quote!(.user_agent(#user_agent)));
+        }
+
+        for header in self.headers.iter() {
+            stream.extend(header.compile_default());
+        }
+
+        if let Some(timeout) = self.timeout {
+            let timeout = compile_duration(timeout);
+            stream.extend(quote!(.timeout(#timeout)));
+        }
+
+        if let Some(read_timeout) = self.read_timeout {
+            let read_timeout = compile_duration(read_timeout);
+            stream.extend(quote!(.read_timeout(#read_timeout)));
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            let connect_timeout = compile_duration(connect_timeout);
+            stream.extend(quote!(.connect_timeout(#connect_timeout)));
+        }
+
+        if let Some(connection_verbose) = self.connection_verbose {
+            stream.extend(quote!(.connection_verbose(#connection_verbose)));
+        }
+
+        if let Some(cookie_store) = self.cookie_store {
+            stream.extend(quote!(.cookie_store(#cookie_store)));
+        }
+
+        if let Some(value) = self.api_key.as_ref().and_then(|api_key| api_key.value.as_ref()) {
+            stream.extend(self.compile_api_key(compile_interpolated(value)));
+        }
+
+        stream
+    }
+
+    fn compile_api_key(&self, value: TokenStream2) -> TokenStream2 {
+        let api_key = self.api_key.clone().unwrap_or_default();
+        let name = api_key.name.unwrap_or_else(|| match api_key.location {
+            ApiKeyLocation::Header => DEFAULT_API_KEY_HEADER.to_string(),
+            _ => DEFAULT_API_KEY_PARAMETER.to_string()
+        });
+        let location = match api_key.location {
+            ApiKeyLocation::Header => quote!(clientix::client::auth::api_key::ApiKeyLocation::Header),
+            ApiKeyLocation::Query => quote!(clientix::client::auth::api_key::ApiKeyLocation::Query),
+            ApiKeyLocation::Cookie => quote!(clientix::client::auth::api_key::ApiKeyLocation::Cookie),
+        };
+
+        quote!(.api_key(#location, #name, #value))
+    }
+
     fn compile_client(&self) -> TokenStream2 {
         let client_struct_name = self.get_ident();
         let client_visibility = self.get_vis();
@@ -153,6 +459,15 @@ impl ClientConfig {
             .map(|method| method.compile_definition())
             .collect::<Vec<_>>();
 
+        let client_hedge_metrics = match self.async_supported {
+            true => quote! {
+                pub fn hedge_metrics(&self, method: &str) -> clientix::client::hedge::HedgeMetrics {
+                    self.client.hedge_metrics(method)
+                }
+            },
+            false => TokenStream2::new()
+        };
+
         TokenStream2::from(quote! {
             #client_visibility struct #client_struct_name {
                 client: #client_type,
@@ -167,6 +482,12 @@ impl ClientConfig {
                 pub fn new() -> Self {
                     #client_struct_name::config().setup()
                 }
+
+                pub fn cookie_jar(&self) -> Option<std::sync::Arc<clientix::client::cookie::ClientixCookieJar>> {
+                    self.config.cookie_jar()
+                }
+
+                #client_hedge_metrics
             }
 
             impl #client_struct_name {
@@ -186,6 +507,7 @@ impl ClientConfig {
             Err(err) => panic!("{}", err)
         };
 
+        self.parse_headers(&input);
         self.item = Some(input);
 
         let trait_items = self.item.clone().expect("missing item trait").items;
@@ -208,6 +530,22 @@ impl ClientConfig {
                     self.url = Some(meta.value()?.parse::<LitStr>()?.value());
                     Ok(())
                 },
+                ref path if path.is_ident("urls") => {
+                    let input = meta.value()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    self.urls = Punctuated::<LitStr, syn::Token![,]>::parse_terminated(&content)?
+                        .iter()
+                        .map(LitStr::value)
+                        .collect();
+                    Ok(())
+                },
+                ref path if path.is_ident("load_balancing") => {
+                    let strategy = meta.value()?.parse::<LitStr>()?;
+                    self.load_balancing = Some(BalanceStrategy::try_from(strategy.value())
+                        .map_err(|_| syn::Error::new(strategy.span(), format!("invalid load balancing strategy: {}, expected round_robin, random or least_outstanding", strategy.value())))?);
+                    Ok(())
+                },
                 ref path if path.is_ident("path") => {
                     self.path = Some(meta.value()?.parse::<LitStr>()?.value());
                     Ok(())
@@ -216,6 +554,54 @@ impl ClientConfig {
                     self.async_supported = meta.value()?.parse::<LitBool>()?.value();
                     Ok(())
                 }
+                ref path if path.is_ident("user_agent") => {
+                    self.user_agent = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                }
+                ref path if path.is_ident("timeout") => {
+                    self.timeout = Some(parse_duration_attr(meta.value()?.parse::<LitStr>()?)?);
+                    Ok(())
+                }
+                ref path if path.is_ident("read_timeout") => {
+                    self.read_timeout = Some(parse_duration_attr(meta.value()?.parse::<LitStr>()?)?);
+                    Ok(())
+                }
+                ref path if path.is_ident("connect_timeout") => {
+                    self.connect_timeout = Some(parse_duration_attr(meta.value()?.parse::<LitStr>()?)?);
+                    Ok(())
+                }
+                ref path if path.is_ident("connection_verbose") => {
+                    self.connection_verbose = Some(meta.value()?.parse::<LitBool>()?.value());
+                    Ok(())
+                }
+                ref path if path.is_ident("cookie_store") => {
+                    self.cookie_store = Some(meta.value()?.parse::<LitBool>()?.value());
+                    Ok(())
+                }
+                ref path if path.is_ident("api_key") => {
+                    let mut api_key = ApiKeyConfig::default();
+                    meta.parse_nested_meta(|nested| {
+                        match nested.path {
+                            ref path if path.is_ident("in") => {
+                                let location = nested.value()?.parse::<LitStr>()?;
+                                api_key.location = ApiKeyLocation::try_from(location.value())
+                                    .map_err(|_| syn::Error::new(location.span(), format!("invalid api key location: {}, expected header, query or cookie", location.value())))?;
+                                Ok(())
+                            }
+                            ref path if path.is_ident("name") => {
+                                api_key.name = Some(nested.value()?.parse::<LitStr>()?.value());
+                                Ok(())
+                            }
+                            ref path if path.is_ident("value") => {
+                                api_key.value = Some(nested.value()?.parse::<LitStr>()?.value());
+                                Ok(())
+                            }
+                            _ => Err(nested.error("unexpected api_key parameter"))
+                        }
+                    })?;
+                    self.api_key = Some(api_key);
+                    Ok(())
+                }
                 _ => Err(meta.error(format!("unexpected client parameter: {}", meta.path.get_ident().map(Ident::to_string).unwrap_or_default())))
             }
         });
@@ -226,6 +612,19 @@ impl ClientConfig {
         };
     }
 
+    fn parse_headers(&mut self, item: &ItemTrait) {
+        item.attrs.iter()
+            .filter(|attr| attr.path().is_ident(HEADER_CLIENT_MACRO))
+            .for_each(|attr| {
+                let tokens = match &attr.meta {
+                    Meta::List(value) => value.tokens.to_token_stream(),
+                    _ => TokenStream2::new(),
+                };
+
+                self.headers.push(HeaderConfig::parse_stream(tokens, true));
+            });
+    }
+
     fn get_ident(&self) -> Ident {
         self.item.clone().expect("missing client name").ident
     }
@@ -244,6 +643,18 @@ impl ClientConfig {
 
 }
 
+fn parse_duration_attr(value: LitStr) -> syn::Result<Duration> {
+    parse_duration(value.value().as_str()).map_err(|_| {
+        syn::Error::new(value.span(), format!("invalid duration: {}, expected e.g. 500ms, 30s, 2m, 1h", value.value()))
+    })
+}
+
+fn compile_duration(duration: Duration) -> TokenStream2 {
+    let secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+    quote!(std::time::Duration::new(#secs, #nanos))
+}
+
 pub fn parse_client(item: TokenStream, attrs: TokenStream) -> TokenStream {
     let client_config = ClientConfig::create(item, attrs);
 
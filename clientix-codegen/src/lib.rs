@@ -9,16 +9,30 @@ use clientix_core::prelude::reqwest::Method;
 use crate::client::parse_client;
 use crate::method::parse_header;
 use crate::method::parse_method;
+use crate::method::parse_paginated;
 
 /**
 A procedural macro for building an HTTP client. It includes the following attributes:
-- url - the base part of the client’s URL, e.g. http://localhost:8080
+- url - the base part of the client’s URL, e.g. http://localhost:8080, or unix:///var/run/app.sock to send requests over a unix socket (unix targets only)
+- urls - replicas of the same service, e.g. ["http://10.0.0.1:8080", "http://10.0.0.2:8080"], requests are load balanced across them
+- load_balancing - how replicas are picked: round_robin (default), random or least_outstanding
 - path - an additional part of the URL path that precedes method paths
 - async - if true, the client is asynchronous; otherwise, it is blocking
+- user_agent - the User-Agent header sent with every request
+- timeout - total request timeout, e.g. 30s, 500ms, 1m
+- read_timeout - async clients only: timeout for each read of the response, e.g. 30s, 500ms, 1m
+- connect_timeout - connect timeout, e.g. 2s, 500ms
+- connection_verbose - if true, connection events are logged
+- cookie_store - if true, cookies received from the server are stored and sent back (see the cookie_jar method of the client)
+- api_key - api key sent with every request: api_key(in = "header" | "query" | "cookie", name = "X-Api-Key", value = "${API_KEY}"),
+  the value is optional and can be set with the api_key builder method; the key is sensitive and redacted from errors
+
+Headers applied to every method of the client are declared with #[header] attributes placed after #[clientix].
 
 Example:
 ```
-#[clientix(url = "http://localhost:8080")]
+#[clientix(url = "http://localhost:8080", user_agent = "example/1.0", timeout = "30s", connect_timeout = "2s")]
+#[header(name = "X-Client", value = "example")]
 trait ExampleClient {
 
     #[get(path = "/", consumes = "application/json", produces = "application/json")]
@@ -27,6 +41,14 @@ trait ExampleClient {
 }
 ```
 
+The url, path, user_agent, api_key and client header values may reference environment variables as ${NAME} or
+${NAME:-default}; they are resolved when the client is created. A variable that is neither set nor has a default
+is returned as a ClientixError by try_setup(), while setup() and new() panic on it. Example:
+```
+#[clientix(url = "${PAYMENTS_URL:-http://localhost:8080}")]
+trait PaymentsClient {}
+```
+
 The client also supports configuring parameters imperatively. Example:
 ```
 let client = ExampleClient::config()
@@ -34,6 +56,148 @@ let client = ExampleClient::config()
     .path("/test")
     .setup();
 ```
+
+Or from a TOML, JSON or YAML file section (url, path, user_agent, headers, timeouts, auth). Example:
+```
+let client = ExampleClient::config()
+    .from_config("clients.toml", "clients.payments")?
+    .setup();
+```
+
+Tokens can be obtained by an auth provider, e.g. OAuth2 client credentials. The token is cached until it expires
+and the request is retried once with a fresh token when the server responds with 401. The token endpoint is reached
+with the TLS, proxy and resolver settings of the client, unless a client is set with http_client or blocking_http_client. Example:
+```
+let client = ExampleClient::config()
+    .auth_provider(OAuth2Provider::builder("https://auth.example.com/token", "client-id").client_secret("secret").build())
+    .setup();
+```
+
+HTTP Digest authentication (MD5 and SHA-256, qop=auth) answers the server challenge and reuses the nonce across requests:
+```
+let client = ExampleClient::config()
+    .digest_auth("user", "password")
+    .setup();
+```
+
+A signer runs right before a request is sent and sees the final method, url, headers and body bytes.
+HmacSigner and SigV4Signer are built in, custom signers implement ClientixSigner. Example:
+```
+let client = ExampleClient::config()
+    .signer(SigV4Signer::new("access-key", "secret-key", "us-east-1", "s3"))
+    .setup();
+```
+
+TLS can be customized with extra root certificates (PEM), a client certificate for mutual TLS and SHA-256 certificate
+fingerprint pins, matched against the server's own certificate. danger_accept_invalid_certs disables chain validation and should only be used for local testing. Example:
+```
+let client = ExampleClient::config()
+    .tls_root_certificate(include_bytes!("ca.pem"))
+    .tls_identity(include_bytes!("client.pem"), include_bytes!("client.key"))
+    .tls_pin("AB:CD:...")
+    .setup();
+```
+
+Proxies are configured per scheme (http, https or all, including socks5://) with optional basic auth and a no-proxy list.
+Explicit proxies replace the ones detected from HTTP_PROXY/HTTPS_PROXY/NO_PROXY, env_proxy(false) disables the detection. Example:
+```
+let client = ExampleClient::config()
+    .proxy(ClientixProxy::https("http://proxy:3128").basic_auth("user", "password"))
+    .no_proxy("localhost,.internal")
+    .setup();
+```
+
+Redirects are followed up to 10 times by default and the visited urls are available from ClientixResponse::redirects.
+Authorization and Cookie headers are dropped when a redirect leaves the origin. The policy can be changed
+to RedirectPolicy::none(), limited(max), same_origin() or custom(callback). Example:
+```
+let client = ExampleClient::config()
+    .redirect(RedirectPolicy::same_origin())
+    .setup();
+```
+
+Responses encoded with gzip, brotli, deflate or zstd are decoded transparently and the encodings are advertised
+in Accept-Encoding. Each of them can be switched off on the builder. Example:
+```
+let client = ExampleClient::config()
+    .zstd(false)
+    .setup();
+```
+
+The HTTP version is negotiated by default, http1_only and http2_prior_knowledge (h2c) force it; the negotiated
+version is reported by ClientixResponse::version. Connection pooling and sockets can be tuned as well. Example:
+```
+let client = ExampleClient::config()
+    .http2_prior_knowledge()
+    .pool_idle_timeout(Duration::from_secs(30))
+    .pool_max_idle_per_host(8)
+    .tcp_keepalive(Duration::from_secs(60))
+    .local_address("10.0.0.5".parse().unwrap())
+    .setup();
+```
+
+Host names can be pinned to addresses without touching /etc/hosts, the Host header and TLS server name stay
+those of the url. A custom resolver implements ClientixResolver, static overrides take precedence over it. Example:
+```
+let client = ExampleClient::config()
+    .resolve("api.example.com", &["10.0.0.7:0".parse().unwrap()])
+    .setup();
+```
+
+With several urls a replica that keeps failing (connection errors, 502, 503, 504) is ejected for a cool-down,
+3 failures and 30 seconds by default. Idempotent requests (GET, HEAD, PUT, DELETE, OPTIONS) that fail are
+retried once on every other replica. Example:
+```
+let client = ExampleClient::config()
+    .urls(&["http://10.0.0.1:8080", "http://10.0.0.2:8080"])
+    .load_balancing(BalanceStrategy::LeastOutstanding)
+    .endpoint_ejection(5, Duration::from_secs(10))
+    .setup();
+```
+
+A circuit breaker fails requests fast with ClientixError::CircuitOpen once the failure rate (transport errors and 5xx
+responses) over the latest requests crosses the threshold. After the open duration a few probe requests decide whether
+it closes again. Circuits are kept per client or per generated method. Example:
+```
+let client = ExampleClient::config()
+    .circuit_breaker(CircuitBreakerConfig::default()
+        .failure_rate(0.5)
+        .minimum_requests(10)
+        .open_duration(Duration::from_secs(30))
+        .half_open_probes(2)
+        .per_method()
+        .on_state_change(|event| println!("{}: {:?} -> {:?}", event.circuit(), event.from(), event.to())))
+    .setup();
+```
+
+Client-side quotas combine a token bucket and a limit of concurrent requests. By default a request waits for a free
+slot, with fail_fast it returns ClientixError::RateLimited instead. A 429 response blocks the bucket for its Retry-After
+and X-RateLimit-Remaining caps the tokens left; without a rate, a remaining budget of 0 pauses requests like a 429.
+Methods can override the client limit with rate_limit and max_in_flight; limit_mode alone keeps the client limit and
+only changes whether the method waits or fails. Example:
+```
+let client = ExampleClient::config()
+    .rate_limit(RateLimitConfig::new().rate(10, Duration::from_secs(1)).max_in_flight(5))
+    .setup();
+```
+
+A private HTTP cache (RFC 9111) keeps GET responses in memory or on disk. Fresh responses are served without
+a request, stale ones are revalidated with If-None-Match/If-Modified-Since and a 304 answer counts as a hit.
+ClientixResponse::cache_status tells Hit, Revalidated or Miss. Responses to requests with credentials (auth, an api key,
+a signer, a cookie jar, Cookie or a sensitive header) are only stored when marked Cache-Control: public. Example:
+```
+let client = ExampleClient::config()
+    .cache(DiskCache::new("/var/cache/example"))
+    .setup();
+```
+
+Async clients can coalesce concurrent GET requests of the same method with the same url, query and vary headers into
+a single call, every caller gets its own copy of the response. Methods returning streams are never coalesced. Example:
+```
+let client = AsyncExampleClient::config()
+    .coalesce(&["Authorization", "Accept-Language"])
+    .setup();
+```
 */
 #[proc_macro_attribute]
 pub fn clientix(attrs: TokenStream, item: TokenStream) -> TokenStream {
@@ -46,14 +210,33 @@ A procedural macro for building an HTTP GET method of trait. It includes the fol
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- hedge - async clients only: sends a second identical request when no response arrives within the delay, e.g. 50ms, or the percentile of latencies seen so far, e.g. p95 (String)
 
 GET method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
 
+The #[query] macro includes the following attributes:
+- name - query parameter name, the argument name is used by default (String)
+- default_value - value sent when an Option argument is None or a list is empty (String)
+- style - list serialization style: repeat (a=1&a=2, default), comma (a=1,2), brackets (a[]=1&a[]=2) (String)
+- flatten - serializes a struct argument into separate query parameters
+
+The #[header] argument macro includes the following attributes:
+- name - HTTP header name, the argument name is used by default (String)
+- sensitive - sensitive HTTP header value (true/false)
+
+Option arguments that are None are not sent.
+
 Example:
 ```
 #[get(path = "/{path_query}", consumes = "application/json", produces = "application/json")]
@@ -71,13 +254,21 @@ A procedural macro for building an HTTP POST method of trait. It includes the fo
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- idempotency_key - sends an Idempotency-Key header with a UUID created once per call and kept for its retries; such requests may fail over to another replica (true/false)
 
 POST method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
+- #[idempotency_key] - passes the Idempotency-Key explicitly, a generated key is used when an Option is None (simple types, String, Option)
 
 Example:
 ```
@@ -98,13 +289,21 @@ A procedural macro for building an HTTP PUT method of trait. It includes the fol
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- idempotency_key - sends an Idempotency-Key header with a UUID created once per call and kept for its retries; such requests may fail over to another replica (true/false)
 
 PUT method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
+- #[idempotency_key] - passes the Idempotency-Key explicitly, a generated key is used when an Option is None (simple types, String, Option)
 
 Example:
 ```
@@ -125,13 +324,21 @@ A procedural macro for building an HTTP DELETE method of trait. It includes the
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- idempotency_key - sends an Idempotency-Key header with a UUID created once per call and kept for its retries; such requests may fail over to another replica (true/false)
 
 DELETE method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
+- #[idempotency_key] - passes the Idempotency-Key explicitly, a generated key is used when an Option is None (simple types, String, Option)
 
 Example:
 ```
@@ -150,11 +357,18 @@ A procedural macro for building an HTTP HEAD method of trait. It includes the fo
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- hedge - async clients only: sends a second identical request when no response arrives within the delay, e.g. 50ms, or the percentile of latencies seen so far, e.g. p95 (String)
 
 HEAD method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
 
@@ -175,13 +389,21 @@ A procedural macro for building an HTTP PATCH method of trait. It includes the f
 - path - a part of the URL path (String)
 - consumes - content type for request, support: application/json, application/xml, application/x-www-form-urlencoded (String)
 - produces - accept type for response, support: application/json, application/xml, application/x-www-form-urlencoded (String)
+- compress - compresses the request body and sets Content-Encoding, support: gzip, deflate, br, zstd (String)
+- rate_limit - overrides the client rate limit for this method, e.g. 10/s, 100/m, 5/500ms (String)
+- max_in_flight - overrides the client limit of concurrent requests for this method (usize)
+- limit_mode - wait for a free slot or fail with ClientixError::RateLimited, support: wait, fail (String)
+- idempotency_key - sends an Idempotency-Key header with a UUID created once per call and kept for its retries; such requests may fail over to another replica (true/false)
 
 PATCH method supports argument macros:
 - #[segment] - maps method arguments to path segments (simple types, String)
-- #[query] - maps method arguments to query parameters (simple types, String)
-- #[header] - maps method arguments to request headers (simple types, String)
+- #[query] - maps method arguments to query parameters (simple types, String, Option, Vec, structs with flatten)
+- #[header] - maps method arguments to request headers (simple types, String, Option)
+- #[headers] - maps method arguments to a dynamic set of request headers (HeaderMap, HashMap<String, String>)
+- #[cookie] - maps method arguments to request cookies, name defaults to the argument name (simple types, String, Option)
 - #[body] - maps method arguments to request body (object implemented #[data_transfer])
 - #[placeholder] - maps method arguments to request header placeholders
+- #[idempotency_key] - passes the Idempotency-Key explicitly, a generated key is used when an Option is None (simple types, String, Option)
 
 Example:
 ```
@@ -196,7 +418,7 @@ pub fn patch(attrs: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /**
-A procedural macro for adding HTTP headers to a request. It includes the following attributes:
+A procedural macro for adding HTTP headers to a request or, when placed on a #[clientix] trait, to every request of the client. It includes the following attributes:
 - name - HTTP header name (String)
 - value - HTTP header value (String)
 - sensitive - sensitive HTTP header value (true/false)
@@ -216,6 +438,38 @@ pub fn header(attrs: TokenStream, item: TokenStream) -> TokenStream {
     parse_header(item, attrs)
 }
 
+/**
+A procedural macro for paginated list methods, placed next to #[get]. The method returns ClientixPageStream<T> from async
+clients and ClientixPageIterator<T> from blocking ones; pages are requested lazily, each with the segment, query and
+header arguments of the call, until a page is empty or the last one. It includes the following attributes:
+- strategy - how the next page is found, support: link (default), cursor, offset, page (String)
+  - link - the url with rel="next" of the Link response header
+  - cursor - the value found at the cursor path of the body, sent as the cursor_param query param
+  - offset - offset_param and limit_param query params, the last page is shorter than page_size
+  - page - page_param (from 1) and size_param query params, the last page is shorter than page_size
+- items - dot separated path of the items array in the body, e.g. data or result.items; the body itself by default (String)
+- cursor - dot separated path of the next cursor in the body, e.g. meta.next_cursor (String)
+- cursor_param - query param of the cursor, cursor by default (String)
+- offset_param, limit_param - query params of offset paging, offset and limit by default (String)
+- page_param, size_param - query params of page paging, page and per_page by default (String)
+- page_size - items requested per page with offset and page paging, 20 by default (usize)
+
+Examples:
+```
+#[paginated(strategy = "cursor", cursor = "meta.next", items = "data")]
+#[get(path = "/objects", consumes = "application/json", produces = "application/json")]
+fn objects(&self, #[query] kind: &str) -> ClientixPageStream<Object>;
+
+#[paginated(strategy = "offset", page_size = 100)]
+#[get(path = "/objects", consumes = "application/json", produces = "application/json")]
+fn objects(&self) -> ClientixPageIterator<Object>;
+```
+*/
+#[proc_macro_attribute]
+pub fn paginated(attrs: TokenStream, item: TokenStream) -> TokenStream {
+    parse_paginated(item, attrs)
+}
+
 /**
 A procedural macro for generating DTO objects.
 
@@ -3,7 +3,10 @@ use syn::{Meta, PatType};
 use syn::__private::TokenStream2;
 use clientix_core::core::headers::content_type::ContentType;
 use crate::method::body::BodyConfig;
+use crate::method::cookie::CookieConfig;
 use crate::method::header::HeaderConfig;
+use crate::method::headers::HeadersConfig;
+use crate::method::idempotency_key::IdempotencyKeyConfig;
 use crate::method::placeholder::PlaceholderConfig;
 use crate::method::query::QueryConfig;
 use crate::method::segment::SegmentConfig;
@@ -14,8 +17,11 @@ pub struct ArgumentsConfig {
     segments: Vec<SegmentConfig>,
     queries: Vec<QueryConfig>,
     headers: Vec<HeaderConfig>,
+    header_maps: Vec<HeadersConfig>,
+    cookies: Vec<CookieConfig>,
     placeholders: Vec<PlaceholderConfig>,
     body: Option<BodyConfig>,
+    idempotency_key: Option<IdempotencyKeyConfig>,
     dry_run: bool,
 }
 
@@ -27,8 +33,11 @@ impl ArgumentsConfig {
             segments: vec![],
             queries: vec![],
             headers: vec![],
+            header_maps: vec![],
+            cookies: vec![],
             placeholders: vec![],
             body: None,
+            idempotency_key: None,
             dry_run,
         }
     }
@@ -45,6 +54,14 @@ impl ArgumentsConfig {
         &self.headers
     }
 
+    pub fn header_maps(&self) -> &Vec<HeadersConfig> {
+        &self.header_maps
+    }
+
+    pub fn cookies(&self) -> &Vec<CookieConfig> {
+        &self.cookies
+    }
+
     pub fn placeholders(&self) -> &Vec<PlaceholderConfig> {
         &self.placeholders
     }
@@ -53,6 +70,10 @@ impl ArgumentsConfig {
         self.body.as_ref()
     }
 
+    pub fn idempotency_key(&self) -> Option<&IdempotencyKeyConfig> {
+        self.idempotency_key.as_ref()
+    }
+
     pub fn add(&mut self, pat_type: &mut PatType) {
         let mut not_processed_attrs = Vec::new();
         
@@ -71,6 +92,12 @@ impl ArgumentsConfig {
                 ref path if path.is_ident("header") => {
                     self.headers.push(HeaderConfig::parse_argument(pat_type, attrs, self.dry_run));
                 },
+                ref path if path.is_ident("headers") => {
+                    self.header_maps.push(HeadersConfig::parse_argument(pat_type, attrs, self.dry_run));
+                },
+                ref path if path.is_ident("cookie") => {
+                    self.cookies.push(CookieConfig::parse_argument(pat_type, attrs, self.dry_run));
+                },
                 ref path if path.is_ident("placeholder") => {
                     self.placeholders.push(PlaceholderConfig::parse_argument(pat_type, attrs, self.dry_run));
                 },
@@ -80,6 +107,12 @@ impl ArgumentsConfig {
                         Some(_) => throw_error("multiple body arg", self.dry_run),
                     }
                 },
+                ref path if path.is_ident("idempotency_key") => {
+                    match self.idempotency_key {
+                        None => self.idempotency_key = Some(IdempotencyKeyConfig::parse_argument(pat_type, attrs, self.dry_run)),
+                        Some(_) => throw_error("multiple idempotency_key arg", self.dry_run),
+                    }
+                },
                 ref path if path.is_ident("args") => {
                     self.parse_args_struct(pat_type, self.dry_run);
                 }
@@ -126,6 +159,14 @@ impl ArgumentsConfig {
             }
         }
 
+        for header_map_variable in self.header_maps.iter() {
+            stream.extend(header_map_variable.compile());
+        }
+
+        for cookie_variable in self.cookies.iter() {
+            stream.extend(cookie_variable.compile());
+        }
+
         stream
     }
 
@@ -0,0 +1,62 @@
+use quote::quote;
+use syn::__private::TokenStream2;
+use syn::parse::Parser;
+use syn::{LitStr, PatType};
+use crate::utils::{is_option_type, throw_error};
+
+#[derive(Clone, Default, Debug)]
+pub struct CookieConfig {
+    argument: Option<Box<syn::Pat>>,
+    argument_type: Option<Box<syn::Type>>,
+    name: Option<String>,
+}
+
+impl CookieConfig {
+
+    pub fn parse_stream(attrs: TokenStream2, dry_run: bool) -> Self {
+        let mut cookie = Self::default();
+
+        let parser = syn::meta::parser(|meta| {
+            match meta.path {
+                ref path if path.is_ident("name") => {
+                    cookie.name = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                _ => Err(meta.error(format!("unexpected cookie param: {}", meta.path.get_ident().unwrap())))
+            }
+        });
+
+        match parser.parse2(attrs) {
+            Ok(_) => (),
+            Err(error) => throw_error(error.to_string().as_str(), dry_run),
+        };
+
+        cookie
+    }
+
+    pub fn parse_argument(pat_type: &PatType, attrs: TokenStream2, dry_run: bool) -> Self {
+        let mut cookie = Self::parse_stream(attrs, dry_run);
+        cookie.argument = Some(pat_type.pat.clone());
+        cookie.argument_type = Some(pat_type.ty.clone());
+
+        cookie
+    }
+
+    pub fn compile(&self) -> TokenStream2 {
+        let cookie_argument = self.argument.clone().expect("missing cookie argument");
+        let cookie_name = match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{}", quote! {#cookie_argument})
+        };
+
+        let optional = self.argument_type.as_ref().map(|ty| is_option_type(ty)).unwrap_or(false);
+
+        if optional {
+            quote!(.optional_cookie(#cookie_name, #cookie_argument.as_ref().map(|value| value.to_string())))
+        } else {
+            quote!(.cookie(#cookie_name, #cookie_argument.to_string().as_str()))
+        }
+    }
+
+}
@@ -3,11 +3,12 @@ use syn::__private::TokenStream2;
 use syn::parse::Parser;
 use syn::{LitBool, LitStr, PatType};
 use crate::method::placeholder::PlaceholderConfig;
-use crate::utils::throw_error;
+use crate::utils::{compile_interpolated, is_option_type, throw_error};
 
 #[derive(Clone, Default, Debug)]
 pub struct HeaderConfig {
     argument: Option<Box<syn::Pat>>,
+    argument_type: Option<Box<syn::Type>>,
     name: Option<String>,
     value: Option<String>,
     sensitive: bool,
@@ -19,6 +20,7 @@ impl HeaderConfig {
     pub fn new(name: Option<String>, value: Option<String>) -> Self {
         Self {
             argument: None,
+            argument_type: None,
             name,
             value,
             sensitive: false,
@@ -62,6 +64,7 @@ impl HeaderConfig {
     pub fn parse_argument(pat_type: &PatType, attrs: TokenStream2, dry_run: bool) -> Self {
         let mut header = Self::parse_stream(attrs, dry_run);
         header.argument = Some(pat_type.pat.clone());
+        header.argument_type = Some(pat_type.ty.clone());
 
         header
     }
@@ -72,8 +75,19 @@ impl HeaderConfig {
 
     pub fn compile_with_placeholders(&self, placeholders: &Vec<PlaceholderConfig>) -> TokenStream2 {
         if let Some(header_argument) = &self.argument {
-            let header_id = format!("{}", quote! {#header_argument});
-            quote!(.header(#header_id, #header_argument.to_string().as_str()))
+            let header_id = match &self.name {
+                Some(name) => name.clone(),
+                None => format!("{}", quote! {#header_argument})
+            };
+
+            let optional = self.argument_type.as_ref().map(|ty| is_option_type(ty)).unwrap_or(false);
+            let sensitive = self.sensitive;
+
+            if optional {
+                quote!(.optional_header(#header_id, #header_argument.as_ref().map(|value| value.to_string()), #sensitive))
+            } else {
+                self.compile_header(header_id, quote!(#header_argument.to_string().as_str()))
+            }
         } else {
             if self.name.is_none() && self.value.is_none() {
                 return quote! {}
@@ -96,15 +110,33 @@ impl HeaderConfig {
                     clientix::prelude::strfmt::strfmt(#value, &arguments).expect("failed to format header").as_str()
                 });
 
-                TokenStream2::from(quote! {
-                    .header(#name, {#stream})
-                })
+                self.compile_header(name, quote!({#stream}))
             } else {
-                TokenStream2::from(quote! {
-                    .header(#name, #value)
-                })
+                self.compile_header(name, quote!(#value))
+            }
+        }
+    }
+
+    pub fn compile_default(&self) -> TokenStream2 {
+        match (&self.name, &self.value) {
+            (Some(name), Some(value)) => {
+                let sensitive = self.sensitive;
+                let value = compile_interpolated(value);
+                quote!(.header(#name, #value, #sensitive))
+            },
+            _ => {
+                throw_error("client header requires name and value", self.dry_run);
+                quote!()
             }
         }
     }
 
+    fn compile_header(&self, name: String, value: TokenStream2) -> TokenStream2 {
+        if self.sensitive {
+            quote!(.sensitive_header(#name, #value))
+        } else {
+            quote!(.header(#name, #value))
+        }
+    }
+
 }
\ No newline at end of file
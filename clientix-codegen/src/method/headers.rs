@@ -0,0 +1,62 @@
+use quote::quote;
+use syn::__private::TokenStream2;
+use syn::PatType;
+use syn::parse::Parser;
+use crate::utils::{extract_type_ident, throw_error};
+
+const HEADER_MAP_TYPE: &str = "HeaderMap";
+const HASH_MAP_TYPE: &str = "HashMap";
+
+#[derive(Clone, Default, Debug)]
+pub struct HeadersConfig {
+    argument: Option<Box<syn::Pat>>,
+    argument_type: Option<Box<syn::Type>>,
+    dry_run: bool,
+}
+
+impl HeadersConfig {
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn parse_stream(attrs: TokenStream2, dry_run: bool) -> Self {
+        let mut headers = Self::new();
+        headers.dry_run = dry_run;
+
+        let parser = syn::meta::parser(|meta| {
+            Err(meta.error(format!("unexpected headers param: {}", meta.path.get_ident().unwrap())))
+        });
+
+        match parser.parse2(attrs) {
+            Ok(_) => (),
+            Err(error) => throw_error(error.to_string().as_str(), dry_run),
+        };
+
+        headers
+    }
+
+    pub fn parse_argument(pat_type: &PatType, attrs: TokenStream2, dry_run: bool) -> Self {
+        let mut headers = Self::parse_stream(attrs, dry_run);
+        headers.argument = Some(pat_type.pat.clone());
+        headers.argument_type = Some(pat_type.ty.clone());
+
+        match extract_type_ident(&pat_type.ty).as_deref() {
+            Some(HEADER_MAP_TYPE) | Some(HASH_MAP_TYPE) => (),
+            _ => throw_error("invalid headers type, support: HeaderMap, HashMap<String, String>", dry_run)
+        }
+
+        headers
+    }
+
+    pub fn compile(&self) -> TokenStream2 {
+        let headers_variable = self.argument.clone().expect("missing headers attribute");
+        let headers_type = self.argument_type.clone().expect("missing headers type");
+
+        match extract_type_ident(&headers_type).as_deref() {
+            Some(HEADER_MAP_TYPE) => quote!(.header_map(&#headers_variable)),
+            _ => quote!(.headers(#headers_variable.clone()))
+        }
+    }
+
+}
@@ -0,0 +1,37 @@
+use quote::quote;
+use syn::__private::TokenStream2;
+use syn::PatType;
+use crate::utils::{is_option_type, throw_error};
+
+#[derive(Clone, Default, Debug)]
+pub struct IdempotencyKeyConfig {
+    argument: Option<Box<syn::Pat>>,
+    argument_type: Option<Box<syn::Type>>,
+}
+
+impl IdempotencyKeyConfig {
+
+    pub fn parse_argument(pat_type: &PatType, attrs: TokenStream2, dry_run: bool) -> Self {
+        if !attrs.is_empty() {
+            throw_error("#[idempotency_key] takes no params", dry_run);
+        }
+
+        IdempotencyKeyConfig {
+            argument: Some(pat_type.pat.clone()),
+            argument_type: Some(pat_type.ty.clone())
+        }
+    }
+
+    // an Option argument that is None falls back to a generated key
+    pub fn compile(&self) -> TokenStream2 {
+        let key_argument = self.argument.clone().expect("missing idempotency key argument");
+        let optional = self.argument_type.as_ref().map(|ty| is_option_type(ty)).unwrap_or(false);
+
+        if optional {
+            quote!(.idempotency_key(#key_argument.as_ref().map(|value| value.to_string()).as_deref()))
+        } else {
+            quote!(.idempotency_key(Some(#key_argument.to_string().as_str())))
+        }
+    }
+
+}
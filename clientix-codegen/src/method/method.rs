@@ -1,14 +1,19 @@
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Attribute, FnArg, LitStr, Meta, Signature, TraitItemFn};
+use std::time::Duration;
+use syn::{Attribute, FnArg, LitBool, LitInt, LitStr, Meta, Signature, TraitItemFn};
 use syn::__private::TokenStream2;
 use syn::parse::Parser;
+use clientix_core::client::hedge::HedgePolicy;
+use clientix_core::client::limiter::{LimitMode, RateLimitConfig};
+use clientix_core::core::headers::content_encoding::ContentEncoding;
 use clientix_core::core::headers::content_type::ContentType;
 use clientix_core::prelude::reqwest::header::{ACCEPT, CONTENT_TYPE};
 use clientix_core::prelude::reqwest::Method;
 use crate::method::arguments::ArgumentsConfig;
 use crate::method::header::HeaderConfig;
 use crate::method::output::OutputConfig;
+use crate::method::pagination::PaginationConfig;
 use crate::utils::throw_error;
 
 const GET_METHOD_MACRO: &str = "get";
@@ -18,6 +23,7 @@ const DELETE_METHOD_MACRO: &str = "delete";
 const HEAD_METHOD_MACRO: &str = "head";
 const PATCH_METHOD_MACRO: &str = "patch";
 const HEADER_METHOD_MACRO: &str = "header";
+const PAGINATED_METHOD_MACRO: &str = "paginated";
 
 #[derive(Clone, Default)]
 pub struct MethodConfig {
@@ -27,7 +33,14 @@ pub struct MethodConfig {
     path: Option<String>,
     consumes: Option<ContentType>,
     produces: Option<ContentType>,
+    compress: Option<ContentEncoding>,
+    rate_limit: Option<(u32, Duration)>,
+    max_in_flight: Option<usize>,
+    limit_mode: Option<LimitMode>,
+    hedge: Option<HedgePolicy>,
+    idempotency_key: bool,
     headers: Vec<HeaderConfig>,
+    pagination: Option<PaginationConfig>,
     async_supported: bool,
     dry_run: bool,
     arguments_config: ArgumentsConfig,
@@ -42,6 +55,7 @@ impl MethodConfig {
 
         let attributes = item.attrs.clone();
         method_attrs.parse_macros(HEADER_METHOD_MACRO, &attributes);
+        method_attrs.parse_macros(PAGINATED_METHOD_MACRO, &attributes);
         method_attrs.parse_macros(GET_METHOD_MACRO, &attributes);
         method_attrs.parse_macros(POST_METHOD_MACRO, &attributes);
         method_attrs.parse_macros(PUT_METHOD_MACRO, &attributes);
@@ -86,8 +100,13 @@ impl MethodConfig {
         let compiled_headers = self.compile_headers();
         let compiled_queries = self.compile_queries();
         let compiled_body = self.compile_body();
-        let compiled_result = self.compile_output();
+        let compiled_send = self.compile_send();
         let compiled_method = self.compile_method();
+        let compiled_limit = self.compile_limit();
+        let compiled_hedge = self.compile_hedge();
+        let compiled_streaming = self.compile_streaming();
+        let compiled_idempotency_key = self.compile_idempotency_key();
+        let operation = sig.ident.to_string();
 
         quote! {
             pub #sig {
@@ -95,12 +114,16 @@ impl MethodConfig {
 
                 self.client
                     #compiled_method
+                    .operation(#operation)
+                    #compiled_limit
+                    #compiled_hedge
+                    #compiled_streaming
                     #compiled_path
                     #compiled_headers
+                    #compiled_idempotency_key
                     #compiled_queries
                     #compiled_body
-                    .send()
-                    #compiled_result
+                    #compiled_send
             }
         }
     }
@@ -124,6 +147,10 @@ impl MethodConfig {
     fn compile_headers(&self) -> TokenStream2 {
         let mut stream = self.arguments_config.compile_headers();
 
+        for header in self.headers.iter() {
+            stream.extend(header.compile_with_placeholders(self.arguments_config.placeholders()));
+        }
+
         if let Some(content_type) = self.consumes {
             stream.extend(HeaderConfig::new(Some(CONTENT_TYPE.to_string()), Some(content_type.to_string())).compile());
         }
@@ -140,11 +167,89 @@ impl MethodConfig {
     }
 
     fn compile_body(&self) -> TokenStream2 {
-        self.arguments_config.compile_body(self.consumes)
+        let mut stream = self.arguments_config.compile_body(self.consumes);
+
+        if let Some(encoding) = self.compress {
+            let encoding = encoding.to_string();
+            stream.extend(quote! {
+                .compress(#encoding.to_string().try_into().unwrap())
+            });
+        }
+
+        stream
+    }
+
+    fn compile_limit(&self) -> TokenStream2 {
+        if self.rate_limit.is_none() && self.max_in_flight.is_none() && self.limit_mode.is_none() {
+            return TokenStream2::new();
+        }
+
+        let mut stream = quote!(clientix::client::limiter::RateLimitConfig::new());
+
+        if let Some((requests, per)) = self.rate_limit {
+            let millis = per.as_millis() as u64;
+            stream.extend(quote!(.rate(#requests, std::time::Duration::from_millis(#millis))));
+        }
+
+        if let Some(max_in_flight) = self.max_in_flight {
+            stream.extend(quote!(.max_in_flight(#max_in_flight)));
+        }
+
+        if let Some(LimitMode::Fail) = self.limit_mode {
+            stream.extend(quote!(.fail_fast()));
+        }
+
+        quote!(.rate_limit(#stream))
     }
 
-    fn compile_output(&self) -> TokenStream2 {
-        self.output_config.compile()
+    fn compile_hedge(&self) -> TokenStream2 {
+        let policy = match self.hedge {
+            Some(_) if !self.async_supported => panic!("hedge is only supported by async clients"),
+            Some(HedgePolicy::Fixed(delay)) => {
+                let millis = delay.as_millis() as u64;
+                quote!(clientix::client::hedge::HedgePolicy::Fixed(std::time::Duration::from_millis(#millis)))
+            },
+            Some(HedgePolicy::Percentile(percentile)) => quote!(clientix::client::hedge::HedgePolicy::Percentile(#percentile)),
+            None => return TokenStream2::new()
+        };
+
+        quote!(.hedge(#policy))
+    }
+
+    // the key is created once per call, so retries of the request send the same one
+    fn compile_idempotency_key(&self) -> TokenStream2 {
+        match (self.arguments_config.idempotency_key(), self.idempotency_key) {
+            (Some(argument), _) => argument.compile(),
+            (None, true) => quote!(.idempotency_key(None)),
+            (None, false) => TokenStream2::new()
+        }
+    }
+
+    fn compile_streaming(&self) -> TokenStream2 {
+        match self.async_supported && self.output_config.is_stream() {
+            true => quote!(.streaming()),
+            false => TokenStream2::new()
+        }
+    }
+
+    // pages are requested lazily by the returned stream or iterator, other methods send the request right away
+    fn compile_send(&self) -> TokenStream2 {
+        let pagination = match &self.pagination {
+            Some(pagination) => pagination,
+            None => {
+                let compiled_result = self.output_config.compile();
+                return quote!(.send() #compiled_result);
+            }
+        };
+
+        if !self.output_config.is_pages() {
+            match self.async_supported {
+                true => throw_error("#[paginated] methods of async clients return ClientixPageStream<T>", self.dry_run),
+                false => throw_error("#[paginated] methods of blocking clients return ClientixPageIterator<T>", self.dry_run)
+            }
+        }
+
+        pagination.compile()
     }
 
     fn parse_stream(&mut self, method: Method, item: TokenStream2, attrs: TokenStream2) {
@@ -184,6 +289,7 @@ impl MethodConfig {
             DELETE_METHOD_MACRO => self.parse_method_attrs(Method::DELETE, attrs),
             HEAD_METHOD_MACRO => self.parse_method_attrs(Method::HEAD, attrs),
             PATCH_METHOD_MACRO => self.parse_method_attrs(Method::PATCH, attrs),
+            PAGINATED_METHOD_MACRO => self.pagination = Some(PaginationConfig::parse_stream(attrs, self.dry_run)),
             _ => throw_error("not valid macro", self.dry_run),
         };
     }
@@ -223,6 +329,68 @@ impl MethodConfig {
 
                     Ok(())
                 }
+                ref path if path.is_ident("compress") => {
+                    match meta.value()?.parse::<LitStr>()?.value().try_into() {
+                        Ok(compress) => {
+                            self.compress = Some(compress);
+                        }
+                        Err(_) => {
+                            throw_error("invalid content-encoding for compress, supported: gzip, deflate, br, zstd", self.dry_run);
+                        }
+                    }
+
+                    Ok(())
+                }
+                ref path if path.is_ident("rate_limit") => {
+                    match RateLimitConfig::parse_rate(meta.value()?.parse::<LitStr>()?.value().as_str()) {
+                        Ok(rate) => {
+                            self.rate_limit = Some(rate);
+                        }
+                        Err(_) => {
+                            throw_error("invalid rate_limit, expected e.g. 10/s, 100/m, 5/500ms", self.dry_run);
+                        }
+                    }
+
+                    Ok(())
+                }
+                ref path if path.is_ident("max_in_flight") => {
+                    self.max_in_flight = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+
+                    Ok(())
+                }
+                ref path if path.is_ident("limit_mode") => {
+                    match LimitMode::try_from(meta.value()?.parse::<LitStr>()?.value()) {
+                        Ok(limit_mode) => {
+                            self.limit_mode = Some(limit_mode);
+                        }
+                        Err(_) => {
+                            throw_error("invalid limit_mode, supported: wait, fail", self.dry_run);
+                        }
+                    }
+
+                    Ok(())
+                }
+                ref path if path.is_ident("hedge") => {
+                    match HedgePolicy::try_from(meta.value()?.parse::<LitStr>()?.value().as_str()) {
+                        // the hedged copy reaches the server too, so only safe methods may be sent twice
+                        Ok(_) if !method.is_safe() => {
+                            throw_error("hedge is only supported by GET and HEAD methods", self.dry_run);
+                        }
+                        Ok(hedge) => {
+                            self.hedge = Some(hedge);
+                        }
+                        Err(_) => {
+                            throw_error("invalid hedge, expected a delay like 50ms or a percentile like p95", self.dry_run);
+                        }
+                    }
+
+                    Ok(())
+                }
+                ref path if path.is_ident("idempotency_key") => {
+                    self.idempotency_key = meta.value()?.parse::<LitBool>()?.value();
+
+                    Ok(())
+                }
                 ref path if path.is_ident("produces") => {
                     match meta.value()?.parse::<LitStr>()?.value().try_into() {
                         Ok(produces) => {
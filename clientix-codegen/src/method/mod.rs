@@ -2,18 +2,23 @@ mod output;
 mod segment;
 mod placeholder;
 mod body;
+mod cookie;
+mod idempotency_key;
+mod pagination;
 mod query;
 mod arguments;
 mod method;
 mod header;
+mod headers;
 
 pub use method::*;
+pub use header::HeaderConfig;
+pub use pagination::PaginationConfig;
 
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::__private::TokenStream2;
 use clientix_core::prelude::reqwest::Method;
-use crate::method::header::HeaderConfig;
 
 pub fn parse_method(method: Method, item: TokenStream, attrs: TokenStream) -> TokenStream {
     let method_config = MethodConfig::create(method, item, attrs);
@@ -30,4 +35,9 @@ pub fn parse_method(method: Method, item: TokenStream, attrs: TokenStream) -> To
 pub fn parse_header(item: TokenStream, attrs: TokenStream) -> TokenStream {
     HeaderConfig::parse_stream(TokenStream2::from(attrs), true);
     TokenStream::from(item)
+}
+
+pub fn parse_paginated(item: TokenStream, attrs: TokenStream) -> TokenStream {
+    PaginationConfig::parse_stream(TokenStream2::from(attrs), true);
+    item
 }
\ No newline at end of file
@@ -8,6 +8,8 @@ const CLIENTIX_RESULT_TYPE: &str = "ClientixResult";
 const CLIENTIX_RESPONSE_TYPE: &str = "ClientixResponse";
 const CLIENTIX_STREAM_TYPE: &str = "ClientixStream";
 const CLIENTIX_SSE_STREAM_TYPE: &str = "ClientixSSEStream";
+const CLIENTIX_PAGE_STREAM_TYPE: &str = "ClientixPageStream";
+const CLIENTIX_PAGE_ITERATOR_TYPE: &str = "ClientixPageIterator";
 const OPTION_TYPE: &str = "Option";
 const STRING_TYPE: &str = "String";
 
@@ -34,6 +36,8 @@ pub enum ReturnKind {
     ClientixStream,
     ClientixResponseOfString,
     ClientixResponse,
+    ClientixPageStream,
+    ClientixPageIterator,
     String,
     Other
 }
@@ -79,6 +83,8 @@ impl From<ReturnType> for ReturnKind {
                     (CLIENTIX_STREAM_TYPE, _, _) => ReturnKind::ClientixStream,
                     (CLIENTIX_RESPONSE_TYPE, STRING_TYPE, _) => ReturnKind::ClientixResponseOfString,
                     (CLIENTIX_RESPONSE_TYPE, _, _) => ReturnKind::ClientixResponse,
+                    (CLIENTIX_PAGE_STREAM_TYPE, _, _) => ReturnKind::ClientixPageStream,
+                    (CLIENTIX_PAGE_ITERATOR_TYPE, _, _) => ReturnKind::ClientixPageIterator,
                     (STRING_TYPE, _, _) => ReturnKind::String,
                     _ => ReturnKind::Other
                 }
@@ -95,6 +101,22 @@ impl OutputConfig {
         Self { kind, async_supported, produces, dry_run }
     }
 
+    pub fn is_stream(&self) -> bool {
+        matches!(self.kind,
+            ReturnKind::ClientixResultOfSSEStreamOfString | ReturnKind::ClientixResultOfSSEStream | ReturnKind::ClientixResultOfStream |
+            ReturnKind::OptionOfSSEStreamOfString | ReturnKind::OptionOfSSEStream | ReturnKind::OptionOfStream |
+            ReturnKind::ClientixSSEStreamOfString | ReturnKind::ClientixSSEStream | ReturnKind::ClientixStream)
+    }
+
+    // a paginated method returns a page stream from async clients and a page iterator from blocking ones
+    pub fn is_pages(&self) -> bool {
+        match self.kind {
+            ReturnKind::ClientixPageStream => self.async_supported,
+            ReturnKind::ClientixPageIterator => !self.async_supported,
+            _ => false
+        }
+    }
+
     pub fn compile(&self) -> TokenStream2 {
         match self.kind {
             ReturnKind::Unit => self.compile_unit(),
@@ -117,6 +139,7 @@ impl OutputConfig {
             ReturnKind::ClientixStream => self.compile_bytes_stream(),
             ReturnKind::ClientixResponseOfString => self.compile_text_response(),
             ReturnKind::ClientixResponse => self.compile_object_response(),
+            ReturnKind::ClientixPageStream | ReturnKind::ClientixPageIterator => self.compile_pages(),
             ReturnKind::String => self.compile_text(),
             ReturnKind::Other => self.compile_object()
         }
@@ -349,6 +372,11 @@ impl OutputConfig {
         }
     }
 
+    fn compile_pages(&self) -> TokenStream2 {
+        throw_error("ClientixPageStream and ClientixPageIterator are returned by #[paginated] methods only", self.dry_run);
+        quote!()
+    }
+
     fn compile_async(&self) -> TokenStream2 {
         if self.async_supported {
             quote! {.await}
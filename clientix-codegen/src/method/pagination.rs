@@ -0,0 +1,141 @@
+use quote::quote;
+use syn::__private::TokenStream2;
+use syn::parse::Parser;
+use syn::{LitInt, LitStr};
+use crate::utils::throw_error;
+
+const DEFAULT_CURSOR_PARAM: &str = "cursor";
+const DEFAULT_OFFSET_PARAM: &str = "offset";
+const DEFAULT_LIMIT_PARAM: &str = "limit";
+const DEFAULT_PAGE_PARAM: &str = "page";
+const DEFAULT_SIZE_PARAM: &str = "per_page";
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+enum PageStrategy {
+    #[default]
+    Link,
+    Cursor,
+    Offset,
+    Page
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct PaginationConfig {
+    strategy: PageStrategy,
+    cursor: Option<String>,
+    cursor_param: Option<String>,
+    offset_param: Option<String>,
+    limit_param: Option<String>,
+    page_param: Option<String>,
+    size_param: Option<String>,
+    page_size: Option<usize>,
+    items: Option<String>
+}
+
+impl PaginationConfig {
+
+    pub fn parse_stream(attrs: TokenStream2, dry_run: bool) -> Self {
+        let mut pagination = Self::default();
+
+        let parser = syn::meta::parser(|meta| {
+            match meta.path {
+                ref path if path.is_ident("strategy") => {
+                    pagination.strategy = match meta.value()?.parse::<LitStr>()?.value().as_str() {
+                        "link" => PageStrategy::Link,
+                        "cursor" => PageStrategy::Cursor,
+                        "offset" => PageStrategy::Offset,
+                        "page" => PageStrategy::Page,
+                        _ => {
+                            throw_error("invalid pagination strategy, supported: link, cursor, offset, page", dry_run);
+                            PageStrategy::Link
+                        }
+                    };
+
+                    Ok(())
+                }
+                ref path if path.is_ident("cursor") => {
+                    pagination.cursor = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("cursor_param") => {
+                    pagination.cursor_param = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("offset_param") => {
+                    pagination.offset_param = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("limit_param") => {
+                    pagination.limit_param = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("page_param") => {
+                    pagination.page_param = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("size_param") => {
+                    pagination.size_param = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                ref path if path.is_ident("page_size") => {
+                    pagination.page_size = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+
+                    Ok(())
+                }
+                ref path if path.is_ident("items") => {
+                    pagination.items = Some(meta.value()?.parse::<LitStr>()?.value());
+
+                    Ok(())
+                }
+                _ => Err(meta.error(format!("unexpected pagination param: {}", meta.path.get_ident().unwrap())))
+            }
+        });
+
+        match parser.parse2(attrs) {
+            Ok(_) => (),
+            Err(error) => throw_error(error.to_string().as_str(), dry_run),
+        };
+
+        if pagination.strategy == PageStrategy::Cursor && pagination.cursor.is_none() {
+            throw_error("cursor pagination requires the cursor path, e.g. cursor = \"meta.next\"", dry_run);
+        }
+
+        pagination
+    }
+
+    pub fn compile(&self) -> TokenStream2 {
+        let page_size = self.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+        let mut stream = match self.strategy {
+            PageStrategy::Link => quote!(clientix::client::pagination::Pagination::link()),
+            PageStrategy::Cursor => {
+                let cursor = self.cursor.clone().unwrap_or_default();
+                let cursor_param = self.cursor_param.as_deref().unwrap_or(DEFAULT_CURSOR_PARAM);
+                quote!(clientix::client::pagination::Pagination::cursor(#cursor, #cursor_param))
+            },
+            PageStrategy::Offset => {
+                let offset_param = self.offset_param.as_deref().unwrap_or(DEFAULT_OFFSET_PARAM);
+                let limit_param = self.limit_param.as_deref().unwrap_or(DEFAULT_LIMIT_PARAM);
+                quote!(clientix::client::pagination::Pagination::offset(#offset_param, #limit_param, #page_size))
+            },
+            PageStrategy::Page => {
+                let page_param = self.page_param.as_deref().unwrap_or(DEFAULT_PAGE_PARAM);
+                let size_param = self.size_param.as_deref().unwrap_or(DEFAULT_SIZE_PARAM);
+                quote!(clientix::client::pagination::Pagination::page(#page_param, #size_param, #page_size))
+            }
+        };
+
+        if let Some(items) = &self.items {
+            stream.extend(quote!(.items(#items)));
+        }
+
+        quote!(.paginate(#stream))
+    }
+
+}
@@ -2,13 +2,17 @@ use quote::quote;
 use syn::__private::TokenStream2;
 use syn::{LitStr, PatType};
 use syn::parse::Parser;
-use crate::utils::throw_error;
+use clientix_core::core::query::style::QueryStyle;
+use crate::utils::{extract_option_type, is_list_type, throw_error};
 
 #[derive(Clone, Default, Debug)]
 pub struct QueryConfig {
     argument: Option<Box<syn::Pat>>,
+    argument_type: Option<Box<syn::Type>>,
     name: Option<String>,
     default_value: Option<String>,
+    style: QueryStyle,
+    flatten: bool,
     dry_run: bool,
 }
 
@@ -34,6 +38,19 @@ impl QueryConfig {
 
                     Ok(())
                 }
+                ref path if path.is_ident("style") => {
+                    match meta.value()?.parse::<LitStr>()?.value().try_into() {
+                        Ok(style) => query.style = style,
+                        Err(_) => throw_error("invalid query style, support: repeat, comma, brackets", dry_run)
+                    }
+
+                    Ok(())
+                }
+                ref path if path.is_ident("flatten") => {
+                    query.flatten = true;
+
+                    Ok(())
+                }
                 _ => Err(meta.error(format!("unexpected method param: {}", meta.path.get_ident().unwrap())))
             }
         });
@@ -49,19 +66,61 @@ impl QueryConfig {
     pub fn parse_argument(pat_type: &PatType, attrs: TokenStream2, dry_run: bool) -> Self {
         let mut query = Self::parse_stream(attrs, dry_run);
         query.argument = Some(pat_type.pat.clone());
+        query.argument_type = Some(pat_type.ty.clone());
 
         query
     }
 
     pub fn compile(&self) -> TokenStream2 {
-        let query_variable = self.argument.clone().expect("missing segment attribute");
+        let query_variable = self.argument.clone().expect("missing query attribute");
+        let query_type = self.argument_type.clone().expect("missing query type");
         let query_id = if let Some(name) = &self.name {
             name.clone()
         } else {
             format!("{}", quote! {#query_variable})
         };
-        
-        quote!(.query(#query_id, #query_variable.to_string().as_str()))
+
+        if self.flatten {
+            return quote!(.query_object(&#query_variable));
+        }
+
+        let (query_values, query_style) = match extract_option_type(&query_type) {
+            Some(inner_type) if is_list_type(inner_type) => (
+                quote!(#query_variable.iter().flatten().map(|value| value.to_string()).collect::<Vec<String>>()),
+                self.style
+            ),
+            Some(_) => (
+                quote!(#query_variable.iter().map(|value| value.to_string()).collect::<Vec<String>>()),
+                QueryStyle::Repeat
+            ),
+            None if is_list_type(&query_type) => (
+                quote!(#query_variable.iter().map(|value| value.to_string()).collect::<Vec<String>>()),
+                self.style
+            ),
+            None => return quote!(.query(#query_id, #query_variable.to_string().as_str()))
+        };
+
+        let query_values = match &self.default_value {
+            Some(default_value) => quote! {
+                {
+                    let values = #query_values;
+                    if values.is_empty() { vec![#default_value.to_string()] } else { values }
+                }
+            },
+            None => query_values
+        };
+
+        let query_style = compile_style(query_style);
+
+        quote!(.query_list(#query_id, #query_values, #query_style))
     }
 
+}
+
+fn compile_style(style: QueryStyle) -> TokenStream2 {
+    match style {
+        QueryStyle::Repeat => quote!(clientix::core::query::style::QueryStyle::Repeat),
+        QueryStyle::Comma => quote!(clientix::core::query::style::QueryStyle::Comma),
+        QueryStyle::Brackets => quote!(clientix::core::query::style::QueryStyle::Brackets),
+    }
 }
\ No newline at end of file
@@ -1,7 +1,73 @@
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type};
+use syn::__private::TokenStream2;
+
+const OPTION_TYPE: &str = "Option";
+const VEC_TYPE: &str = "Vec";
+const INTERPOLATION_START: &str = "${";
+
 pub fn throw_error(message: &str, dry_run: bool) {
     if dry_run {
         panic!("{}", message);
     } else {
         eprintln!("{}", message);
     }
-}
\ No newline at end of file
+}
+
+pub fn is_list_type(ty: &Type) -> bool {
+    match strip_reference(ty) {
+        Type::Slice(_) | Type::Array(_) => true,
+        ty => extract_generic_type(ty, VEC_TYPE).is_some()
+    }
+}
+
+pub fn compile_interpolated(value: &str) -> TokenStream2 {
+    if value.contains(INTERPOLATION_START) {
+        quote!(clientix::core::env::resolve(#value, &mut interpolation_error).as_str())
+    } else {
+        quote!(#value)
+    }
+}
+
+pub fn is_option_type(ty: &Type) -> bool {
+    extract_generic_type(ty, OPTION_TYPE).is_some()
+}
+
+pub fn extract_option_type(ty: &Type) -> Option<&Type> {
+    extract_generic_type(ty, OPTION_TYPE)
+}
+
+pub fn extract_type_ident(ty: &Type) -> Option<String> {
+    match strip_reference(ty) {
+        Type::Path(type_path) => type_path.path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None
+    }
+}
+
+fn extract_generic_type<'a>(ty: &'a Type, type_name: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = strip_reference(ty) else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != type_name {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn strip_reference(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(reference) => strip_reference(&reference.elem),
+        Type::Paren(paren) => strip_reference(&paren.elem),
+        Type::Group(group) => strip_reference(&group.elem),
+        ty => ty
+    }
+}
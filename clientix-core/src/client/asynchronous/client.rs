@@ -1,14 +1,42 @@
 use std::sync::{Arc, Mutex};
-use http::Method;
+use http::header::USER_AGENT;
+use http::{HeaderMap, HeaderValue, Method};
 use reqwest::Client as ReqwestClient;
 use crate::client::asynchronous::request::AsyncRequest;
-use crate::client::ClientConfig;
+use crate::client::auth::ClientixAuthProvider;
+use crate::client::auth::api_key::{ApiKey, ApiKeyLocation};
+use crate::client::auth::digest::DigestProviders;
+use crate::client::balancer::LoadBalancer;
+use crate::client::breaker::CircuitBreaker;
+use crate::client::cache::HttpCache;
+use crate::client::coalesce::Coalescing;
+use crate::client::limiter::RateLimits;
+use crate::client::cookie::ClientixCookieJar;
+use crate::client::hedge::{HedgeMetrics, Hedging};
+use crate::client::redirect::RedirectPolicy;
+use crate::client::resolver::ResolverAdapter;
+use crate::client::signer::ClientixSigner;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::client::{ClientConfig, HttpVersion};
 
 #[derive(Clone)]
 pub struct AsyncClient {
     pub client: Arc<Mutex<ReqwestClient>>,
     pub url: String,
-    pub path: String
+    pub path: String,
+    pub auth: Option<Arc<dyn ClientixAuthProvider>>,
+    pub digests: Arc<DigestProviders>,
+    pub signer: Option<Arc<dyn ClientixSigner>>,
+    pub api_key: Option<ApiKey>,
+    pub cookie_jar: Option<Arc<ClientixCookieJar>>,
+    pub redirect: RedirectPolicy,
+    pub balancer: Option<Arc<LoadBalancer>>,
+    pub breaker: Option<Arc<CircuitBreaker>>,
+    pub limits: Arc<RateLimits>,
+    pub cache: Option<Arc<HttpCache>>,
+    pub hedging: Arc<Hedging>,
+    pub coalescing: Option<Arc<Coalescing>>,
+    pub headers: HeaderMap
 }
 
 impl AsyncClient {
@@ -37,36 +65,152 @@ impl AsyncClient {
         AsyncRequest::builder(self.clone(), Method::PATCH)
     }
 
+    pub fn hedge_metrics(&self, operation: &str) -> HedgeMetrics {
+        self.hedging.metrics(operation)
+    }
+
 }
 
-impl From<ClientConfig> for AsyncClient {
+impl TryFrom<ClientConfig> for AsyncClient {
+
+    type Error = ClientixError;
 
-    fn from(config: ClientConfig) -> Self {
+    fn try_from(config: ClientConfig) -> ClientixResult<Self> {
         let mut client = ReqwestClient::builder();
 
-        if let Some(user_agent) = config.user_agent {
+        if let Some(user_agent) = &config.user_agent {
             client = client.user_agent(user_agent.clone());
         }
 
-        if !config.headers.is_empty() {
-            client = client.default_headers(config.headers.clone());
-        }
-
         if let Some(timeout) = config.timeout {
             client = client.timeout(timeout);
         }
 
-        if let Some(connect_timeout) = config.connect_timeout {
-            client = client.connect_timeout(connect_timeout);
+        if let Some(read_timeout) = config.read_timeout {
+            client = client.read_timeout(read_timeout);
         }
 
         client = client.connection_verbose(config.connection_verbose);
 
-        let url = config.url.expect("missing url");
+        client = match config.http_version {
+            HttpVersion::Auto => client,
+            HttpVersion::Http1Only => client.http1_only(),
+            HttpVersion::Http2PriorKnowledge => client.http2_prior_knowledge()
+        };
+
+        if let Some(pool_idle_timeout) = config.pool_idle_timeout {
+            client = client.pool_idle_timeout(pool_idle_timeout);
+        }
+
+        if let Some(max) = config.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+
+        if let Some(interval) = config.tcp_keepalive {
+            client = client.tcp_keepalive(interval);
+        }
+
+        client = client
+            .tcp_nodelay(config.tcp_nodelay)
+            .local_address(config.local_address);
+
+        // a response is decoded transparently for every enabled encoding listed in Accept-Encoding
+        client = client
+            .gzip(config.gzip)
+            .brotli(config.brotli)
+            .deflate(config.deflate)
+            .zstd(config.zstd);
+
+        // redirects are followed by clientix itself to apply the policy and record the history
+        client = client.redirect(reqwest::redirect::Policy::none());
+
+        if let Some(cookie_jar) = &config.cookie_jar {
+            client = client.cookie_provider(cookie_jar.clone());
+        }
+
+        #[cfg(unix)]
+        if let Some(unix_socket) = config.unix_socket() {
+            client = client.unix_socket(unix_socket);
+        }
+
+        client = transport(client, &config)?;
+
+        // providers sending requests of their own, e.g. to an oauth2 token endpoint, go through the same transport
+        if let Some(auth) = &config.auth_provider {
+            let auth_client = transport(ReqwestClient::builder(), &config)?
+                .build()
+                .map_err(|err| config_error("failed to build async auth client", Some(err.into())))?;
+            auth.bind(auth_client);
+        }
+
+        let auth = config.auth_provider.clone();
+        let signer = config.signer.clone();
+        let api_key = config.api_key.clone();
+        let cookie_jar = config.cookie_jar.clone();
+        let redirect = config.redirect.clone();
+        let balancer = config.load_balancer()?.map(Arc::new);
+        let breaker = config.circuit_breaker.clone().map(|breaker| Arc::new(CircuitBreaker::new(breaker)));
+        let limits = Arc::new(RateLimits::new(config.rate_limit.as_ref()));
+        // cookies from the jar are only added once the request is sent, so a jar counts as a credential up front
+        let authenticated = config.auth_provider.is_some() || config.api_key.is_some() || config.signer.is_some() || config.cookie_jar.is_some();
+        let cache = config.cache.clone().map(|storage| Arc::new(HttpCache::new(storage).authenticated(authenticated)));
+        let hedging = Arc::new(Hedging::default());
+        let coalescing = config.coalesce.as_deref().map(|vary| match &config.api_key {
+            Some(api_key) if api_key.location() == ApiKeyLocation::Header => Arc::new(Coalescing::new(vary).credential(api_key.name())),
+            _ => Arc::new(Coalescing::new(vary))
+        });
+        let mut headers = config.headers.clone();
+        if let Some(user_agent) = config.user_agent.as_ref().and_then(|user_agent| HeaderValue::from_str(user_agent).ok()) {
+            headers.insert(USER_AGENT, user_agent);
+        }
+
+        let url = config.base_url().ok_or_else(|| config_error("missing url", None))?;
         let path = config.path.unwrap_or(String::new());
-        let client = Arc::new(Mutex::new(client.build().expect("failed to build async client")));
+        let client = Arc::new(Mutex::new(client.build().map_err(|err| config_error("failed to build async client", Some(err.into())))?));
+
+        let digests = Arc::new(DigestProviders::default());
+
+        Ok(AsyncClient { client, url, path, auth, digests, signer, api_key, cookie_jar, redirect, balancer, breaker, limits, cache, hedging, coalescing, headers })
+    }
+
+}
+
+fn config_error(message: &str, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message).build(), source)
+}
+
+// connection settings shared by the client and the transport handed to its auth provider
+fn transport(mut client: reqwest::ClientBuilder, config: &ClientConfig) -> ClientixResult<reqwest::ClientBuilder> {
+    if let Some(connect_timeout) = config.connect_timeout {
+        client = client.connect_timeout(connect_timeout);
+    }
+
+    if let Some(resolver) = &config.resolver {
+        client = client.dns_resolver(Arc::new(ResolverAdapter::new(resolver.clone())));
+    }
+
+    // static overrides win over the resolver
+    for (host, addrs) in config.resolve_overrides.iter() {
+        client = client.resolve_to_addrs(host, addrs);
+    }
+
+    if !config.env_proxy {
+        client = client.no_proxy();
+    }
+
+    // explicit proxies replace the ones detected from the environment
+    for proxy in config.proxies.iter() {
+        client = client.proxy(proxy.build(config.no_proxy.as_deref())?);
+    }
+
+    if config.tls.is_configured() {
+        let mut tls = config.tls.rustls_config()?;
+        if config.http_version == HttpVersion::Http1Only {
+            tls.alpn_protocols.retain(|protocol| protocol.as_slice() != b"h2");
+        }
 
-        AsyncClient { client, url, path }
+        client = client.use_preconfigured_tls(tls);
     }
 
+    Ok(client)
 }
@@ -6,4 +6,5 @@ pub mod stream;
 pub use client::*;
 pub use request::*;
 pub use response::*;
+pub use stream::pages::*;
 pub use bytes::*;
\ No newline at end of file
@@ -1,9 +1,12 @@
 use bytes::Bytes;
 use futures_util::TryStreamExt;
-use reqwest::Response;
+use reqwest::{Response, StatusCode};
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
 use crate::client::asynchronous::stream::ClientixStream;
 use crate::client::asynchronous::stream::sse::ClientixSSEStream;
+use crate::client::cache::CacheStatus;
+use crate::client::redirect::RedirectHistory;
 use crate::client::response::{ClientixError, ClientixResponse, ClientixResult};
 
 pub struct AsyncResponseHandler {
@@ -16,9 +19,24 @@ impl AsyncResponseHandler {
         AsyncResponseHandler { result }
     }
 
+    pub(crate) fn outcome(&self) -> Result<StatusCode, &ClientixError> {
+        self.result.as_ref().map(Response::status)
+    }
+
+    pub(crate) fn into_result(self) -> ClientixResult<Response> {
+        self.result
+    }
+
+    pub(crate) fn response_head(&self) -> Option<(StatusCode, &HeaderMap)> {
+        self.result.as_ref().ok().map(|response| (response.status(), response.headers()))
+    }
+
     pub async fn bytes(self) -> ClientixResult<ClientixResponse<Bytes>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -27,7 +45,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.bytes().await?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -53,6 +71,9 @@ impl AsyncResponseHandler {
     pub async fn text(self) -> ClientixResult<ClientixResponse<String>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -61,7 +82,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.text().await?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -70,6 +91,9 @@ impl AsyncResponseHandler {
     pub async fn text_with_encoding(self, encoding: &str) -> ClientixResult<ClientixResponse<String>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -78,7 +102,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.text_with_charset(encoding).await?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -91,6 +115,9 @@ impl AsyncResponseHandler {
     pub async fn json<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -99,7 +126,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_json::from_str::<T>(response.text().await?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -112,6 +139,9 @@ impl AsyncResponseHandler {
     pub async fn xml<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -120,7 +150,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_xml_rs::from_str::<T>(response.text().await?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -133,6 +163,9 @@ impl AsyncResponseHandler {
     pub async fn urlencoded<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -141,7 +174,7 @@ impl AsyncResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_urlencoded::from_str::<T>(response.text().await?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
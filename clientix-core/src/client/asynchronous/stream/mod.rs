@@ -1,3 +1,4 @@
+pub mod pages;
 pub mod sse;
 
 use std::net::SocketAddr;
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use futures_core::Stream;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use crate::client::asynchronous::request::AsyncRequest;
+use crate::client::pagination::Pager;
+use crate::client::response::ClientixResult;
+
+// the items of a paginated endpoint, the next page is requested once the current one is consumed;
+// an error is yielded once and ends the stream
+pub struct ClientixPageStream<T> {
+    stream: Pin<Box<dyn Stream<Item = ClientixResult<T>>>>
+}
+
+struct PageState<T> {
+    request: AsyncRequest,
+    pager: Pager,
+    items: VecDeque<T>
+}
+
+impl<T> ClientixPageStream<T> where T: DeserializeOwned + 'static {
+
+    pub(crate) fn new(request: AsyncRequest, pager: Pager) -> Self {
+        let state = PageState { request, pager, items: VecDeque::new() };
+        let stream = stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.items.pop_front() {
+                    return Some((Ok(item), state));
+                }
+
+                if !state.pager.has_next() {
+                    return None;
+                }
+
+                if let Err(error) = state.fetch().await {
+                    state.pager.finish();
+                    return Some((Err(error), state));
+                }
+            }
+        });
+
+        ClientixPageStream { stream: Box::pin(stream) }
+    }
+
+    pub async fn execute<F>(mut self, mut handle: F) where F: FnMut(ClientixResult<T>) {
+        while let Some(result) = self.stream.next().await {
+            handle(result);
+        }
+    }
+
+    pub async fn collect(self) -> ClientixResult<Vec<T>> {
+        self.stream.try_collect().await
+    }
+
+}
+
+impl<T> PageState<T> where T: DeserializeOwned {
+
+    async fn fetch(&mut self) -> ClientixResult<()> {
+        self.pager.check_next()?;
+        let request = match self.request.next_page(&self.pager) {
+            Some(request) => request,
+            None => return Ok(())
+        };
+
+        let response = request.send().await.bytes().await?;
+        let items = self.pager.read(&response.url(), response.status(), response.headers(), response.body().as_ref())?;
+        self.items.extend(items);
+
+        Ok(())
+    }
+
+}
+
+impl<T> Stream for ClientixPageStream<T> {
+    type Item = ClientixResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
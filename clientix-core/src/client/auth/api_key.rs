@@ -0,0 +1,155 @@
+use std::fmt::{Debug, Display, Formatter};
+use http::header::COOKIE;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Url;
+use serde::Deserialize;
+use crate::client::cookie::encode_cookie_value;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const REDACTED: &str = "REDACTED";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+    Cookie
+}
+
+#[derive(Clone)]
+pub struct ApiKey {
+    location: ApiKeyLocation,
+    name: String,
+    value: String
+}
+
+impl ApiKey {
+
+    pub fn new(location: ApiKeyLocation, name: &str, value: &str) -> Self {
+        ApiKey { location, name: name.to_string(), value: value.to_string() }
+    }
+
+    pub fn location(&self) -> ApiKeyLocation {
+        self.location
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn apply(&self, url: &mut Url, headers: &mut HeaderMap) -> ClientixResult<()> {
+        match self.location {
+            ApiKeyLocation::Header => {
+                let name = HeaderName::from_bytes(self.name.as_bytes())
+                    .map_err(|err| api_key_error(format!("invalid api key header name: {}", self.name), Some(err.into())))?;
+                headers.insert(name, self.header_value(self.value.clone())?);
+            },
+            ApiKeyLocation::Query => {
+                url.query_pairs_mut().append_pair(self.name.as_str(), self.value.as_str());
+            },
+            ApiKeyLocation::Cookie => {
+                let value = encode_cookie_value(self.value.as_str());
+                let cookie = match headers.get(COOKIE).and_then(|value| value.to_str().ok()) {
+                    Some(cookies) => format!("{cookies}; {}={value}", self.name),
+                    None => format!("{}={value}", self.name)
+                };
+                headers.insert(COOKIE, self.header_value(cookie)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn redact(&self, url: &mut Url) {
+        if self.location != ApiKeyLocation::Query || url.query().is_none() {
+            return;
+        }
+
+        let pairs = url.query_pairs()
+            .map(|(key, value)| match key == self.name {
+                true => (key.to_string(), REDACTED.to_string()),
+                false => (key.to_string(), value.to_string())
+            })
+            .collect::<Vec<_>>();
+
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    // removes the key from a request leaving the origin it was issued for
+    pub fn strip(&self, url: &mut Url, headers: &mut HeaderMap) {
+        match self.location {
+            ApiKeyLocation::Header => {
+                headers.remove(self.name.as_str());
+            },
+            ApiKeyLocation::Query => {
+                if url.query().is_none() {
+                    return;
+                }
+
+                let pairs = url.query_pairs()
+                    .filter(|(key, _)| *key != self.name)
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<Vec<_>>();
+
+                match pairs.is_empty() {
+                    true => url.set_query(None),
+                    false => {
+                        url.query_pairs_mut().clear().extend_pairs(pairs);
+                    }
+                }
+            },
+            ApiKeyLocation::Cookie => {
+                headers.remove(COOKIE);
+            }
+        }
+    }
+
+    fn header_value(&self, value: String) -> ClientixResult<HeaderValue> {
+        let mut value = HeaderValue::from_str(value.as_str())
+            .map_err(|err| api_key_error("invalid api key value".to_string(), Some(err.into())))?;
+        value.set_sensitive(true);
+
+        Ok(value)
+    }
+
+}
+
+impl Debug for ApiKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("location", &self.location)
+            .field("name", &self.name)
+            .field("value", &REDACTED)
+            .finish()
+    }
+}
+
+impl TryFrom<String> for ApiKeyLocation {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "header" => Ok(ApiKeyLocation::Header),
+            "query" => Ok(ApiKeyLocation::Query),
+            "cookie" => Ok(ApiKeyLocation::Cookie),
+            _ => Err(())
+        }
+    }
+}
+
+impl Display for ApiKeyLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ApiKeyLocation::Header => "header",
+            ApiKeyLocation::Query => "query",
+            ApiKeyLocation::Cookie => "cookie"
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+fn api_key_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Auth(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use futures_util::future::BoxFuture;
+use http::header::WWW_AUTHENTICATE;
+use http::{HeaderMap, HeaderValue};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use crate::client::auth::{AuthRequest, ClientixAuthProvider};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const DIGEST_SCHEME: &str = "digest";
+const QOP_AUTH: &str = "auth";
+
+pub struct DigestAuthProvider {
+    username: String,
+    password: String,
+    session: Mutex<Option<DigestSession>>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    qop: bool,
+    stale: bool
+}
+
+struct DigestSession {
+    challenge: DigestChallenge,
+    nonce_count: u32
+}
+
+// providers for request-level credentials, shared by the requests of a client so a challenge is answered once
+#[derive(Default)]
+pub struct DigestProviders {
+    providers: Mutex<HashMap<(String, String), Arc<DigestAuthProvider>>>
+}
+
+impl DigestAuthProvider {
+
+    pub fn new(username: &str, password: &str) -> Self {
+        DigestAuthProvider {
+            username: username.to_string(),
+            password: password.to_string(),
+            session: Mutex::new(None)
+        }
+    }
+
+    fn authorization(&self, request: &AuthRequest) -> ClientixResult<Option<HeaderValue>> {
+        let mut session = self.session.lock()
+            .map_err(|err| auth_error(format!("digest session poisoned: {err}")))?;
+
+        let session = match session.as_mut() {
+            Some(session) => session,
+            None => return Ok(None)
+        };
+
+        session.nonce_count += 1;
+
+        let uri = match request.url().query() {
+            Some(query) => format!("{}?{}", request.url().path(), query),
+            None => request.url().path().to_string()
+        };
+
+        let cnonce = format!("{:032x}", rand::random::<u128>());
+        let authorization = session.challenge.authorization(
+            self.username.as_str(),
+            self.password.as_str(),
+            request.method().as_str(),
+            uri.as_str(),
+            session.nonce_count,
+            cnonce.as_str()
+        );
+
+        let mut authorization = HeaderValue::from_str(authorization.as_str())
+            .map_err(|err| auth_error(format!("invalid digest authorization: {err}")))?;
+        authorization.set_sensitive(true);
+
+        Ok(Some(authorization))
+    }
+
+}
+
+impl DigestProviders {
+
+    pub fn provider(&self, username: &str, password: &str) -> Arc<DigestAuthProvider> {
+        let mut providers = self.providers.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        providers.entry((username.to_string(), password.to_string()))
+            .or_insert_with(|| Arc::new(DigestAuthProvider::new(username, password)))
+            .clone()
+    }
+
+}
+
+impl ClientixAuthProvider for DigestAuthProvider {
+
+    fn authorize<'a>(&'a self, request: &'a AuthRequest) -> BoxFuture<'a, ClientixResult<Option<HeaderValue>>> {
+        Box::pin(async move { self.authorization(request) })
+    }
+
+    fn authorize_blocking(&self, request: &AuthRequest) -> ClientixResult<Option<HeaderValue>> {
+        self.authorization(request)
+    }
+
+    fn unauthorized(&self, _request: &AuthRequest, headers: &HeaderMap) -> bool {
+        let challenge = headers.get_all(WWW_AUTHENTICATE).iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(DigestChallenge::parse)
+            .max_by_key(|challenge| challenge.algorithm.strength());
+
+        let (challenge, mut session) = match (challenge, self.session.lock()) {
+            (Some(challenge), Ok(session)) => (challenge, session),
+            _ => return false
+        };
+
+        // the same nonce without the stale flag means the credentials were rejected, retrying is pointless
+        if let Some(current) = session.as_ref() {
+            if current.challenge.nonce == challenge.nonce && !challenge.stale {
+                *session = None;
+                return false;
+            }
+        }
+
+        *session = Some(DigestSession { challenge, nonce_count: 0 });
+
+        true
+    }
+
+}
+
+impl DigestAlgorithm {
+
+    fn parse(value: &str) -> Option<DigestAlgorithm> {
+        match value.to_ascii_uppercase().as_str() {
+            "MD5" => Some(DigestAlgorithm::Md5),
+            "MD5-SESS" => Some(DigestAlgorithm::Md5Sess),
+            "SHA-256" => Some(DigestAlgorithm::Sha256),
+            "SHA-256-SESS" => Some(DigestAlgorithm::Sha256Sess),
+            _ => None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Md5Sess => "MD5-sess",
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha256Sess => "SHA-256-sess"
+        }
+    }
+
+    fn strength(&self) -> u8 {
+        match self {
+            DigestAlgorithm::Md5 | DigestAlgorithm::Md5Sess => 0,
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Sha256Sess => 1
+        }
+    }
+
+    fn session(&self) -> bool {
+        matches!(self, DigestAlgorithm::Md5Sess | DigestAlgorithm::Sha256Sess)
+    }
+
+    fn hash(&self, data: &str) -> String {
+        let bytes = match self {
+            DigestAlgorithm::Md5 | DigestAlgorithm::Md5Sess => Md5::digest(data.as_bytes()).to_vec(),
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Sha256Sess => Sha256::digest(data.as_bytes()).to_vec()
+        };
+
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+}
+
+impl DigestChallenge {
+
+    pub fn parse(header: &str) -> Vec<DigestChallenge> {
+        parse_challenges(header).into_iter()
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(DIGEST_SCHEME))
+            .filter_map(|(_, params)| DigestChallenge::from_params(params))
+            .collect()
+    }
+
+    fn from_params(mut params: HashMap<String, String>) -> Option<DigestChallenge> {
+        let algorithm = match params.get("algorithm") {
+            Some(algorithm) => DigestAlgorithm::parse(algorithm)?,
+            None => DigestAlgorithm::Md5
+        };
+
+        let qop = match params.get("qop") {
+            Some(qop) => qop.split(',').any(|qop| qop.trim().eq_ignore_ascii_case(QOP_AUTH)).then_some(true)?,
+            None => false
+        };
+
+        Some(DigestChallenge {
+            realm: params.remove("realm")?,
+            nonce: params.remove("nonce")?,
+            opaque: params.remove("opaque"),
+            algorithm,
+            qop,
+            stale: params.get("stale").is_some_and(|stale| stale.eq_ignore_ascii_case("true"))
+        })
+    }
+
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    pub fn response(&self, username: &str, password: &str, method: &str, uri: &str, nonce_count: u32, cnonce: &str) -> String {
+        let algorithm = self.algorithm;
+
+        let mut ha1 = algorithm.hash(format!("{username}:{}:{password}", self.realm).as_str());
+        if algorithm.session() {
+            ha1 = algorithm.hash(format!("{ha1}:{}:{cnonce}", self.nonce).as_str());
+        }
+
+        let ha2 = algorithm.hash(format!("{method}:{uri}").as_str());
+
+        match self.qop {
+            true => algorithm.hash(format!("{ha1}:{}:{nonce_count:08x}:{cnonce}:{QOP_AUTH}:{ha2}", self.nonce).as_str()),
+            false => algorithm.hash(format!("{ha1}:{}:{ha2}", self.nonce).as_str())
+        }
+    }
+
+    pub fn authorization(&self, username: &str, password: &str, method: &str, uri: &str, nonce_count: u32, cnonce: &str) -> String {
+        let response = self.response(username, password, method, uri, nonce_count, cnonce);
+
+        let mut authorization = format!(
+            r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={}, response="{}""#,
+            quote(username), quote(self.realm.as_str()), quote(self.nonce.as_str()), quote(uri), self.algorithm.name(), response
+        );
+
+        if let Some(opaque) = &self.opaque {
+            authorization.push_str(format!(r#", opaque="{}""#, quote(opaque)).as_str());
+        }
+
+        if self.qop {
+            authorization.push_str(format!(r#", qop={QOP_AUTH}, nc={nonce_count:08x}, cnonce="{cnonce}""#).as_str());
+        }
+
+        authorization
+    }
+
+}
+
+fn parse_challenges(header: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut challenges: Vec<(String, HashMap<String, String>)> = vec![];
+    let mut rest = header;
+
+    loop {
+        rest = rest.trim_start_matches(|char: char| char == ',' || char.is_whitespace());
+        if rest.is_empty() {
+            return challenges;
+        }
+
+        let end = rest.find(|char: char| char == '=' || char == ',' || char.is_whitespace()).unwrap_or(rest.len());
+        let token = &rest[..end];
+        rest = rest[end..].trim_start();
+
+        match rest.strip_prefix('=') {
+            Some(value) => {
+                let (value, remaining) = parse_param_value(value.trim_start());
+                if let Some((_, params)) = challenges.last_mut() {
+                    params.insert(token.to_ascii_lowercase(), value);
+                }
+                rest = remaining;
+            },
+            None => challenges.push((token.to_string(), HashMap::new()))
+        }
+    }
+}
+
+fn parse_param_value(value: &str) -> (String, &str) {
+    match value.strip_prefix('"') {
+        Some(quoted) => {
+            let mut result = String::new();
+            let mut chars = quoted.char_indices();
+
+            while let Some((index, char)) = chars.next() {
+                match char {
+                    '\\' => if let Some((_, escaped)) = chars.next() { result.push(escaped) },
+                    '"' => return (result, &quoted[index + 1..]),
+                    _ => result.push(char)
+                }
+            }
+
+            (result, "")
+        },
+        None => {
+            let end = value.find(',').unwrap_or(value.len());
+            (value[..end].trim().to_string(), &value[end..])
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn auth_error(message: String) -> ClientixError {
+    ClientixError::Auth(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
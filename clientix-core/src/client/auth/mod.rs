@@ -0,0 +1,64 @@
+pub mod api_key;
+pub mod digest;
+pub mod oauth2;
+
+use std::fmt::{Debug, Formatter};
+
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, HeaderValue, Method};
+use reqwest::Url;
+use crate::client::response::ClientixResult;
+
+pub trait ClientixAuthProvider: Send + Sync {
+
+    fn authorize<'a>(&'a self, request: &'a AuthRequest) -> BoxFuture<'a, ClientixResult<Option<HeaderValue>>>;
+
+    fn authorize_blocking(&self, request: &AuthRequest) -> ClientixResult<Option<HeaderValue>>;
+
+    fn unauthorized(&self, request: &AuthRequest, headers: &HeaderMap) -> bool;
+
+    // a client built with the transport settings (TLS, proxies, resolver) of the client using the provider
+    fn bind(&self, _client: reqwest::Client) {}
+
+    fn bind_blocking(&self, _client: reqwest::blocking::Client) {}
+
+}
+
+impl Debug for dyn ClientixAuthProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientixAuthProvider")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthRequest {
+    method: Method,
+    url: Url,
+    authorization: Option<HeaderValue>
+}
+
+impl AuthRequest {
+
+    pub fn new(method: Method, url: Url) -> Self {
+        AuthRequest { method, url, authorization: None }
+    }
+
+    // the Authorization the request was sent with, so a provider can tell whether a 401 rejected its current credentials
+    pub fn with_authorization(mut self, authorization: Option<HeaderValue>) -> Self {
+        self.authorization = authorization;
+        self
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn authorization(&self) -> Option<&HeaderValue> {
+        self.authorization.as_ref()
+    }
+
+}
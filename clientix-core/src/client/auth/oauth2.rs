@@ -0,0 +1,292 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use futures_util::future::BoxFuture;
+use futures_util::lock::Mutex as AsyncMutex;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, HeaderValue};
+use serde::Deserialize;
+use crate::client::auth::{AuthRequest, ClientixAuthProvider};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::headers::content_type::ContentType;
+
+const CLIENT_CREDENTIALS_GRANT: &str = "client_credentials";
+const REFRESH_TOKEN_GRANT: &str = "refresh_token";
+const DEFAULT_EXPIRY_SKEW: Duration = Duration::from_secs(30);
+
+pub struct OAuth2Provider {
+    token_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    scope: Option<String>,
+    expiry_skew: Duration,
+    state: Mutex<OAuth2State>,
+    async_refresh: AsyncMutex<()>,
+    blocking_refresh: Mutex<()>,
+    async_client: OnceLock<reqwest::Client>,
+    blocking_client: OnceLock<reqwest::blocking::Client>
+}
+
+pub struct OAuth2ProviderBuilder {
+    token_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    scope: Option<String>,
+    refresh_token: Option<String>,
+    expiry_skew: Duration,
+    async_client: Option<reqwest::Client>,
+    blocking_client: Option<reqwest::blocking::Client>
+}
+
+#[derive(Default)]
+struct OAuth2State {
+    token: Option<OAuth2Token>,
+    refresh_token: Option<String>
+}
+
+#[derive(Clone)]
+struct OAuth2Token {
+    authorization: HeaderValue,
+    expires_at: Option<Instant>
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: Option<String>,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>
+}
+
+impl OAuth2Provider {
+
+    pub fn builder(token_url: &str, client_id: &str) -> OAuth2ProviderBuilder {
+        OAuth2ProviderBuilder::new(token_url, client_id)
+    }
+
+    fn cached_token(&self) -> Option<HeaderValue> {
+        let state = self.state.lock().ok()?;
+        let token = state.token.as_ref()?;
+
+        match token.expires_at {
+            Some(expires_at) if expires_at <= Instant::now() + self.expiry_skew => None,
+            _ => Some(token.authorization.clone())
+        }
+    }
+
+    fn token_form(&self) -> ClientixResult<String> {
+        let refresh_token = self.state.lock()
+            .map_err(|err| auth_error(format!("oauth2 state poisoned: {err}"), None))?
+            .refresh_token
+            .clone();
+
+        let mut form = match &refresh_token {
+            Some(refresh_token) => vec![("grant_type", REFRESH_TOKEN_GRANT), ("refresh_token", refresh_token.as_str())],
+            None => vec![("grant_type", CLIENT_CREDENTIALS_GRANT)],
+        };
+
+        form.push(("client_id", self.client_id.as_str()));
+
+        if let Some(client_secret) = &self.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope.as_str()));
+        }
+
+        serde_urlencoded::to_string(form).map_err(|err| auth_error("invalid oauth2 token request".to_string(), Some(err.into())))
+    }
+
+    fn store_token(&self, status: http::StatusCode, body: &str) -> ClientixResult<HeaderValue> {
+        if !status.is_success() {
+            let rejected = status == http::StatusCode::BAD_REQUEST || status == http::StatusCode::UNAUTHORIZED;
+            if let (true, Ok(mut state)) = (rejected, self.state.lock()) {
+                state.refresh_token = None;
+            }
+
+            return Err(auth_error(format!("oauth2 token endpoint responded with {status}"), None));
+        }
+
+        let response = serde_json::from_str::<TokenResponse>(body)
+            .map_err(|err| auth_error("invalid oauth2 token response".to_string(), Some(err.into())))?;
+
+        let token_type = match response.token_type {
+            Some(token_type) if !token_type.eq_ignore_ascii_case("bearer") => token_type,
+            _ => "Bearer".to_string()
+        };
+
+        let mut authorization = HeaderValue::from_str(format!("{token_type} {}", response.access_token).as_str())
+            .map_err(|err| auth_error("invalid oauth2 access token".to_string(), Some(err.into())))?;
+        authorization.set_sensitive(true);
+
+        let token = OAuth2Token {
+            authorization: authorization.clone(),
+            expires_at: response.expires_in.map(|expires_in| Instant::now() + Duration::from_secs(expires_in))
+        };
+
+        let mut state = self.state.lock()
+            .map_err(|err| auth_error(format!("oauth2 state poisoned: {err}"), None))?;
+
+        state.token = Some(token);
+        if response.refresh_token.is_some() {
+            state.refresh_token = response.refresh_token;
+        }
+
+        Ok(authorization)
+    }
+
+    async fn fetch_token(&self) -> ClientixResult<HeaderValue> {
+        let _refresh = self.async_refresh.lock().await;
+
+        if let Some(authorization) = self.cached_token() {
+            return Ok(authorization);
+        }
+
+        let response = self.async_client.get_or_init(reqwest::Client::new)
+            .post(self.token_url.as_str())
+            .header(CONTENT_TYPE, ContentType::ApplicationXWwwFormUrlEncoded.to_string())
+            .body(self.token_form()?)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        self.store_token(status, body.as_str())
+    }
+
+    fn fetch_token_blocking(&self) -> ClientixResult<HeaderValue> {
+        let _refresh = self.blocking_refresh.lock()
+            .map_err(|err| auth_error(format!("oauth2 refresh lock poisoned: {err}"), None))?;
+
+        if let Some(authorization) = self.cached_token() {
+            return Ok(authorization);
+        }
+
+        let response = self.blocking_client.get_or_init(reqwest::blocking::Client::new)
+            .post(self.token_url.as_str())
+            .header(CONTENT_TYPE, ContentType::ApplicationXWwwFormUrlEncoded.to_string())
+            .body(self.token_form()?)
+            .send()?;
+
+        let status = response.status();
+        let body = response.text()?;
+
+        self.store_token(status, body.as_str())
+    }
+
+}
+
+impl ClientixAuthProvider for OAuth2Provider {
+
+    fn authorize<'a>(&'a self, _request: &'a AuthRequest) -> BoxFuture<'a, ClientixResult<Option<HeaderValue>>> {
+        Box::pin(async move {
+            match self.cached_token() {
+                Some(authorization) => Ok(Some(authorization)),
+                None => self.fetch_token().await.map(Some)
+            }
+        })
+    }
+
+    fn authorize_blocking(&self, _request: &AuthRequest) -> ClientixResult<Option<HeaderValue>> {
+        match self.cached_token() {
+            Some(authorization) => Ok(Some(authorization)),
+            None => self.fetch_token_blocking().map(Some)
+        }
+    }
+
+    // a client set on the builder, or bound by an earlier client, is kept
+    fn bind(&self, client: reqwest::Client) {
+        let _ = self.async_client.set(client);
+    }
+
+    fn bind_blocking(&self, client: reqwest::blocking::Client) {
+        let _ = self.blocking_client.set(client);
+    }
+
+    // a concurrent request may have replaced the token already, only the one the server rejected is dropped
+    fn unauthorized(&self, request: &AuthRequest, _headers: &HeaderMap) -> bool {
+        match self.state.lock() {
+            Ok(mut state) => {
+                let rejected = match (&state.token, request.authorization()) {
+                    (Some(token), Some(authorization)) => token.authorization == *authorization,
+                    _ => true
+                };
+
+                if rejected {
+                    state.token = None;
+                }
+
+                true
+            },
+            Err(_) => false
+        }
+    }
+
+}
+
+impl OAuth2ProviderBuilder {
+
+    fn new(token_url: &str, client_id: &str) -> Self {
+        OAuth2ProviderBuilder {
+            token_url: token_url.to_string(),
+            client_id: client_id.to_string(),
+            client_secret: None,
+            scope: None,
+            refresh_token: None,
+            expiry_skew: DEFAULT_EXPIRY_SKEW,
+            async_client: None,
+            blocking_client: None
+        }
+    }
+
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
+    pub fn refresh_token(mut self, refresh_token: &str) -> Self {
+        self.refresh_token = Some(refresh_token.to_string());
+        self
+    }
+
+    pub fn expiry_skew(mut self, expiry_skew: Duration) -> Self {
+        self.expiry_skew = expiry_skew;
+        self
+    }
+
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.async_client = Some(client);
+        self
+    }
+
+    pub fn blocking_http_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.blocking_client = Some(client);
+        self
+    }
+
+    pub fn build(self) -> OAuth2Provider {
+        OAuth2Provider {
+            token_url: self.token_url,
+            client_id: self.client_id,
+            client_secret: self.client_secret,
+            scope: self.scope,
+            expiry_skew: self.expiry_skew,
+            state: Mutex::new(OAuth2State { token: None, refresh_token: self.refresh_token }),
+            async_refresh: AsyncMutex::new(()),
+            blocking_refresh: Mutex::new(()),
+            async_client: self.async_client.map(OnceLock::from).unwrap_or_default(),
+            blocking_client: self.blocking_client.map(OnceLock::from).unwrap_or_default()
+        }
+    }
+
+}
+
+fn auth_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Auth(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
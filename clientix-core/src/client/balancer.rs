@@ -0,0 +1,203 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const DEFAULT_EJECTION_FAILURES: u32 = 3;
+const DEFAULT_EJECTION_COOL_DOWN: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceStrategy {
+    #[default]
+    RoundRobin,
+    Random,
+    LeastOutstanding
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EjectionConfig {
+    failures: u32,
+    cool_down: Duration
+}
+
+#[derive(Debug)]
+pub struct LoadBalancer {
+    endpoints: Vec<Endpoint>,
+    strategy: BalanceStrategy,
+    ejection: EjectionConfig,
+    next: AtomicUsize
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    outstanding: AtomicUsize,
+    failures: AtomicU32,
+    ejected_until: Mutex<Option<Instant>>
+}
+
+// keeps an endpoint counted as outstanding until the attempt is over
+pub struct EndpointGuard<'a> {
+    endpoint: &'a Endpoint
+}
+
+impl TryFrom<String> for BalanceStrategy {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "round_robin" => Ok(BalanceStrategy::RoundRobin),
+            "random" => Ok(BalanceStrategy::Random),
+            "least_outstanding" => Ok(BalanceStrategy::LeastOutstanding),
+            _ => Err(())
+        }
+    }
+}
+
+impl Default for EjectionConfig {
+
+    fn default() -> Self {
+        EjectionConfig { failures: DEFAULT_EJECTION_FAILURES, cool_down: DEFAULT_EJECTION_COOL_DOWN }
+    }
+
+}
+
+impl EjectionConfig {
+
+    pub fn new(failures: u32, cool_down: Duration) -> Self {
+        EjectionConfig { failures: failures.max(1), cool_down }
+    }
+
+}
+
+impl LoadBalancer {
+
+    pub fn new(urls: &[String], strategy: BalanceStrategy, ejection: EjectionConfig) -> ClientixResult<LoadBalancer> {
+        let endpoints = urls.iter()
+            .map(|url| Url::parse(url)
+                .map(|url| Endpoint::new(url.as_str().trim_end_matches('/')))
+                .map_err(|err| balancer_error(format!("invalid endpoint url: {url}"), Some(err.into()))))
+            .collect::<ClientixResult<Vec<_>>>()?;
+
+        Ok(LoadBalancer { endpoints, strategy, ejection, next: AtomicUsize::new(0) })
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn url(&self, index: usize) -> &str {
+        self.endpoints[index].url.as_str()
+    }
+
+    // the index of the endpoint a request url was built for, requests are always built against the first one
+    pub fn rebase(&self, url: &Url, index: usize) -> Option<Url> {
+        let suffix = url.as_str().strip_prefix(self.endpoints.first()?.url.as_str())?;
+        Url::parse(format!("{}{}", self.endpoints.get(index)?.url, suffix).as_str()).ok()
+    }
+
+    pub fn select(&self, excluded: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let candidates = (0..self.endpoints.len())
+            .filter(|index| !excluded.contains(index))
+            .collect::<Vec<_>>();
+
+        let available = candidates.iter().copied()
+            .filter(|index| !self.endpoints[*index].ejected(now))
+            .collect::<Vec<_>>();
+
+        // with every remaining endpoint ejected the one returning soonest is still better than failing
+        if available.is_empty() {
+            return candidates.into_iter().min_by_key(|index| self.endpoints[*index].ejected_until());
+        }
+
+        let offset = self.next.fetch_add(1, Ordering::Relaxed);
+        match self.strategy {
+            BalanceStrategy::RoundRobin => Some(available[offset % available.len()]),
+            BalanceStrategy::Random => Some(available[rand::random_range(0..available.len())]),
+            BalanceStrategy::LeastOutstanding => (0..available.len())
+                .map(|position| available[(offset + position) % available.len()])
+                .min_by_key(|index| self.endpoints[*index].outstanding.load(Ordering::Relaxed))
+        }
+    }
+
+    pub fn start(&self, index: usize) -> EndpointGuard<'_> {
+        let endpoint = &self.endpoints[index];
+        endpoint.outstanding.fetch_add(1, Ordering::Relaxed);
+
+        EndpointGuard { endpoint }
+    }
+
+    pub fn outstanding(&self, index: usize) -> usize {
+        self.endpoints[index].outstanding.load(Ordering::Relaxed)
+    }
+
+    pub fn ejected(&self, index: usize) -> bool {
+        self.endpoints[index].ejected(Instant::now())
+    }
+
+    // transport errors and gateway errors count against an endpoint, anything else proves it is alive
+    pub fn report(&self, index: usize, status: Option<StatusCode>) -> bool {
+        let endpoint = &self.endpoints[index];
+        let failed = match status {
+            Some(status) => matches!(status, StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT),
+            None => true
+        };
+
+        if !failed {
+            endpoint.failures.store(0, Ordering::Relaxed);
+            return false;
+        }
+
+        if endpoint.failures.fetch_add(1, Ordering::Relaxed) + 1 >= self.ejection.failures {
+            // after the cool-down a single failure is enough to eject the endpoint again
+            endpoint.failures.store(self.ejection.failures - 1, Ordering::Relaxed);
+            if let Ok(mut ejected_until) = endpoint.ejected_until.lock() {
+                *ejected_until = Some(Instant::now() + self.ejection.cool_down);
+            }
+        }
+
+        true
+    }
+
+}
+
+impl Endpoint {
+
+    fn new(url: &str) -> Self {
+        Endpoint {
+            url: url.to_string(),
+            outstanding: AtomicUsize::new(0),
+            failures: AtomicU32::new(0),
+            ejected_until: Mutex::new(None)
+        }
+    }
+
+    fn ejected_until(&self) -> Option<Instant> {
+        self.ejected_until.lock().ok().and_then(|ejected_until| *ejected_until)
+    }
+
+    fn ejected(&self, now: Instant) -> bool {
+        self.ejected_until().is_some_and(|ejected_until| ejected_until > now)
+    }
+
+}
+
+impl Drop for EndpointGuard<'_> {
+
+    fn drop(&mut self) {
+        self.endpoint.outstanding.fetch_sub(1, Ordering::Relaxed);
+    }
+
+}
+
+fn balancer_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -1,7 +1,9 @@
 pub mod client;
+pub mod pages;
 pub mod request;
 pub mod response;
 
 pub use client::*;
+pub use pages::*;
 pub use request::*;
 pub use response::*;
\ No newline at end of file
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use serde::de::DeserializeOwned;
+use crate::client::blocking::request::BlockingRequest;
+use crate::client::pagination::Pager;
+use crate::client::response::ClientixResult;
+
+// the items of a paginated endpoint, the next page is requested once the current one is consumed;
+// an error is yielded once and ends the iteration
+pub struct ClientixPageIterator<T> {
+    request: BlockingRequest,
+    pager: Pager,
+    items: VecDeque<T>
+}
+
+impl<T> ClientixPageIterator<T> where T: DeserializeOwned {
+
+    pub(crate) fn new(request: BlockingRequest, pager: Pager) -> Self {
+        ClientixPageIterator { request, pager, items: VecDeque::new() }
+    }
+
+    fn fetch(&mut self) -> ClientixResult<()> {
+        self.pager.check_next()?;
+        let request = match self.request.next_page(&self.pager) {
+            Some(request) => request,
+            None => return Ok(())
+        };
+
+        let response = request.send().bytes()?;
+        let items = self.pager.read(&response.url(), response.status(), response.headers(), response.body().as_ref())?;
+        self.items.extend(items);
+
+        Ok(())
+    }
+
+}
+
+impl<T> Iterator for ClientixPageIterator<T> where T: DeserializeOwned {
+
+    type Item = ClientixResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+
+            if !self.pager.has_next() {
+                return None;
+            }
+
+            if let Err(error) = self.fetch() {
+                self.pager.finish();
+                return Some(Err(error));
+            }
+        }
+    }
+
+}
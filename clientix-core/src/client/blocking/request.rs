@@ -1,8 +1,21 @@
-use http::Method;
+use std::sync::Arc;
+use std::time::SystemTime;
+use http::header::{AUTHORIZATION, CONTENT_ENCODING};
+use http::{HeaderValue, Method};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Request, Response};
+use serde::de::DeserializeOwned;
 use crate::client::blocking::client::BlockingClient;
+use crate::client::blocking::pages::ClientixPageIterator;
 use crate::client::blocking::response::BlockingResponseHandler;
+use crate::client::auth::{AuthRequest, ClientixAuthProvider};
+use crate::client::cache::{CacheLookup, CacheStatus, HttpCache};
+use crate::client::pagination::{Pager, Pagination};
+use crate::client::redirect::RedirectHistory;
+use crate::client::signer::SignableRequest;
 use crate::client::request::{ClientixRequestBuilder, RequestConfig};
 use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::headers::idempotency_key::IDEMPOTENCY_KEY;
 
 pub struct BlockingRequest {
     client: BlockingClient,
@@ -38,53 +51,323 @@ impl BlockingRequest {
         BlockingRequest::new(client, method)
     }
 
+    // the items of every page, requested lazily with the arguments of this request
+    pub fn paginate<T>(self, pagination: Pagination) -> ClientixPageIterator<T> where T: DeserializeOwned {
+        ClientixPageIterator::new(self, Pager::new(pagination))
+    }
+
+    // the request of the next page, a request that could not be built fails on its first page
+    pub(crate) fn next_page(&mut self, pager: &Pager) -> Option<BlockingRequest> {
+        let config = match &self.result {
+            Ok(()) => pager.next_config(&self.config)?,
+            Err(_) => self.config.clone()
+        };
+
+        Some(BlockingRequest {
+            client: self.client.clone(),
+            method: self.method.clone(),
+            config,
+            result: std::mem::replace(&mut self.result, Ok(()))
+        })
+    }
+
     pub fn send(self) -> BlockingResponseHandler {
+        let breaker = self.client.breaker.clone();
+        let operation = self.operation_name();
+        let permit = match breaker.as_deref().map(|breaker| breaker.acquire(operation.as_str())).transpose() {
+            Ok(permit) => permit,
+            Err(error) => return BlockingResponseHandler::new(Err(error))
+        };
+
+        let limit = self.client.limits.resolve(operation.as_str(), self.config.get_rate_limit());
+        let slot = match &limit {
+            Some(limit) => match limit.acquire_blocking() {
+                Ok(slot) => Some(slot),
+                Err(error) => return BlockingResponseHandler::new(Err(error))
+            },
+            None => None
+        };
+
+        let handler = self.dispatch();
+        drop(slot);
+
+        if let (Some(limit), Some((status, headers))) = (&limit, handler.response_head()) {
+            limit.observe(status, headers);
+        }
+
+        if let Some(permit) = permit {
+            permit.complete(handler.outcome());
+        }
+
+        handler
+    }
+
+    fn operation_name(&self) -> String {
+        match self.config.get_operation() {
+            Some(operation) => operation.to_string(),
+            None => format!("{} {}", self.method, self.config.get_path())
+        }
+    }
+
+    fn dispatch(self) -> BlockingResponseHandler {
         if let Err(error) = self.result {
             return BlockingResponseHandler::new(Err(error));
         }
         
-        let full_path = format!("{}{}", self.client.path, self.config.get_path());
-        let url = format!("{}{}", self.client.url, full_path);
-
-        match self.client.client.lock() {
-            Ok(client) => {
-                let mut request_builder = match self.method {
-                    Method::GET => client.get(url.clone()),
-                    Method::POST => client.post(url.clone()),
-                    Method::PUT => client.put(url.clone()),
-                    Method::DELETE => client.delete(url.clone()),
-                    Method::HEAD => client.head(url.clone()),
-                    Method::PATCH => client.patch(url.clone()),
-                    _ => {
-                        let error_data = ClientixErrorData::builder().message(format!("invalid method: {:?}", self.method).as_str()).build();
-                        return BlockingResponseHandler::new(Err(ClientixError::InvalidRequest(error_data, None)));
-                    },
-                };
-
-                request_builder = request_builder
-                    .headers(self.config.get_headers().clone())
-                    .query(self.config.get_queries());
-
-                request_builder = match self.config.get_body() {
-                    Some(body) => request_builder.body::<String>(body.into()),
-                    None => request_builder,
-                };
-
-                request_builder = match self.config.get_timeout() {
-                    Some(timeout) => request_builder.timeout(timeout),
-                    None => request_builder,
-                };
-
-                match request_builder.send() {
-                    Ok(response) => BlockingResponseHandler::new(Ok(response)),
-                    Err(error) => BlockingResponseHandler::new(Err(ClientixError::Http(ClientixErrorData::new(), Some(error.into()))))
-                }
+        let url = match self.config.get_url() {
+            Some(url) => url.to_string(),
+            None => format!("{}{}{}", self.client.url, self.client.path, self.config.get_path())
+        };
+
+        let client = match self.client.client.lock() {
+            Ok(client) => client.clone(),
+            Err(err) => {
+                let error_data = ClientixErrorData::builder().message(format!("client locked: {:?}", err).as_str()).build();
+                return BlockingResponseHandler::new(Err(ClientixError::Other(error_data, None)));
+            }
+        };
+
+        let mut request_builder = match self.method {
+            Method::GET => client.get(url),
+            Method::POST => client.post(url),
+            Method::PUT => client.put(url),
+            Method::DELETE => client.delete(url),
+            Method::HEAD => client.head(url),
+            Method::PATCH => client.patch(url),
+            _ => {
+                let error_data = ClientixErrorData::builder().message(format!("invalid method: {:?}", self.method).as_str()).build();
+                return BlockingResponseHandler::new(Err(ClientixError::InvalidRequest(error_data, None)));
             },
-            Err(error) => {
-                let error_data = ClientixErrorData::builder().message(format!("client locked: {:?}", error).as_str()).build();
-                BlockingResponseHandler::new(Err(ClientixError::Other(error_data, None)))
+        };
+
+        request_builder = request_builder
+            .headers(self.config.get_headers().clone())
+            .query(self.config.get_queries());
+
+        request_builder = match (self.config.get_body(), self.config.get_compression()) {
+            (Some(body), Some(encoding)) => match encoding.encode(body.as_bytes()) {
+                Ok(encoded) => request_builder.header(CONTENT_ENCODING, encoding.to_string()).body(encoded),
+                Err(error) => return BlockingResponseHandler::new(Err(error))
+            },
+            (Some(body), None) => request_builder.body::<String>(body.into()),
+            (None, _) => request_builder,
+        };
+
+        request_builder = match self.config.get_timeout() {
+            Some(timeout) => request_builder.timeout(timeout),
+            None => request_builder,
+        };
+
+        let mut request = match request_builder.build() {
+            Ok(request) => request,
+            Err(error) => return BlockingResponseHandler::new(Err(ClientixError::InvalidRequest(ClientixErrorData::new(), Some(error.into()))))
+        };
+
+        // client default headers are merged here rather than by reqwest, so redirects can drop credentials among them
+        for (name, value) in self.client.headers.iter() {
+            if !request.headers().contains_key(name) {
+                request.headers_mut().insert(name, value.clone());
             }
         }
+
+        if let Some(api_key) = &self.client.api_key {
+            let mut url = request.url().clone();
+            if let Err(error) = api_key.apply(&mut url, request.headers_mut()) {
+                return BlockingResponseHandler::new(Err(error));
+            }
+
+            *request.url_mut() = url;
+        }
+
+        let cache = self.client.cache.as_deref();
+        let digest = self.config.get_digest_auth()
+            .map(|(username, password)| self.client.digests.provider(username, password) as Arc<dyn ClientixAuthProvider>);
+        let auth = match digest.or_else(|| self.client.auth.clone()) {
+            Some(auth) => auth,
+            None => return BlockingResponseHandler::new(execute(&self.client, &client, request, cache))
+        };
+
+        let auth_request = AuthRequest::new(request.method().clone(), request.url().clone());
+        if let Err(error) = authorize(&mut request, auth.authorize_blocking(&auth_request)) {
+            return BlockingResponseHandler::new(Err(error));
+        }
+
+        let sent = auth_request.clone().with_authorization(request.headers().get(AUTHORIZATION).cloned());
+        let retry = request.try_clone();
+        let response = match execute(&self.client, &client, request, cache) {
+            Ok(response) => response,
+            Err(error) => return BlockingResponseHandler::new(Err(error))
+        };
+
+        match retry {
+            Some(mut retry) if response.status() == StatusCode::UNAUTHORIZED && auth.unauthorized(&sent, response.headers()) => {
+                if let Err(error) = authorize(&mut retry, auth.authorize_blocking(&auth_request)) {
+                    return BlockingResponseHandler::new(Err(error));
+                }
+
+                BlockingResponseHandler::new(execute(&self.client, &client, retry, cache))
+            },
+            _ => BlockingResponseHandler::new(Ok(response))
+        }
     }
 
-}
\ No newline at end of file
+}
+
+// fresh responses are served by the cache and stale ones revalidated, everything else goes to the network
+fn execute(clientix: &BlockingClient, client: &Client, mut request: Request, cache: Option<&HttpCache>) -> ClientixResult<Response> {
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return balance(clientix, client, request)
+    };
+
+    let method = request.method().clone();
+    let url = request.url().clone();
+    let request_headers = request.headers().clone();
+    let stale = match cache.lookup(&method, &url, &request_headers) {
+        CacheLookup::Bypass => {
+            let response = balance(clientix, client, request)?;
+            cache.invalidate(&method, &url, response.status());
+
+            return Ok(response);
+        },
+        CacheLookup::Fresh(response) => return Ok(Response::from(response)),
+        CacheLookup::Stale(cached) => {
+            cached.validate(request.headers_mut());
+            Some(cached)
+        },
+        CacheLookup::Miss => None
+    };
+
+    let request_time = SystemTime::now();
+    let mut response = balance(clientix, client, request)?;
+    let response_time = SystemTime::now();
+
+    if let (Some(cached), StatusCode::NOT_MODIFIED) = (stale, response.status()) {
+        return cache.revalidated(&url, cached, response.headers(), request_time, response_time).map(Response::from);
+    }
+
+    if !cache.storable(&request_headers, response.status(), response.headers()) {
+        cache.forget(&url);
+        response.extensions_mut().insert(CacheStatus::Miss);
+
+        return Ok(response);
+    }
+
+    let (status, version, response_url) = (response.status(), response.version(), response.url().clone());
+    let (headers, extensions) = (response.headers().clone(), response.extensions().clone());
+    let buffered = HttpCache::buffered(status, version, &response_url, &headers, &extensions, response.bytes()?)?;
+    cache.store(&url, &request_headers, &response_url, &buffered, request_time, response_time);
+
+    Ok(Response::from(buffered))
+}
+
+fn balance(clientix: &BlockingClient, client: &Client, mut request: Request) -> ClientixResult<Response> {
+    let balancer = match &clientix.balancer {
+        Some(balancer) if balancer.rebase(request.url(), 0).is_some() => balancer,
+        _ => return follow(clientix, client, request)
+    };
+
+    // only idempotent requests, or ones carrying an Idempotency-Key, are safe to replay on another replica
+    let failover = request.method().is_idempotent() || request.headers().contains_key(IDEMPOTENCY_KEY);
+    let mut tried = vec![];
+
+    loop {
+        let index = match balancer.select(&tried) {
+            Some(index) => index,
+            None => return follow(clientix, client, request)
+        };
+
+        tried.push(index);
+        let retry = match failover && tried.len() < balancer.len() {
+            true => request.try_clone(),
+            false => None
+        };
+
+        if let Some(url) = balancer.rebase(request.url(), index) {
+            *request.url_mut() = url;
+        }
+
+        let result = {
+            let _outstanding = balancer.start(index);
+            follow(clientix, client, request)
+        };
+
+        let failed = balancer.report(index, result.as_ref().ok().map(Response::status));
+        request = match retry {
+            Some(retry) if failed => retry,
+            _ => return result
+        };
+    }
+}
+
+fn follow(clientix: &BlockingClient, client: &Client, mut request: Request) -> ClientixResult<Response> {
+    let mut visited = vec![];
+
+    loop {
+        // the jar is asked on every hop, a redirect may lead to another path or host, or set cookies on the way
+        let next = request.try_clone();
+        if let Some(cookie_jar) = &clientix.cookie_jar {
+            let url = request.url().clone();
+            cookie_jar.apply(&url, request.headers_mut());
+        }
+
+        visited.push(request.url().clone());
+        sign(clientix, &mut request)?;
+
+        let method = request.method().clone();
+        let mut response = client.execute(request)
+            .map_err(|mut error| {
+                if let (Some(api_key), Some(url)) = (&clientix.api_key, error.url_mut()) {
+                    api_key.redact(url);
+                }
+
+                ClientixError::Http(ClientixErrorData::new(), Some(error.into()))
+            })?;
+
+        let target = clientix.redirect.resolve(&method, response.status(), response.headers(), &visited)?;
+        request = match (target, next) {
+            (Some(target), Some(mut next)) => {
+                *next.method_mut() = target.method().clone();
+                let mut url = target.url().clone();
+                target.prepare(&mut url, next.headers_mut(), clientix.api_key.as_ref());
+                *next.url_mut() = url;
+                if !target.keep_body() {
+                    *next.body_mut() = None;
+                }
+
+                next
+            },
+            _ => {
+                visited.pop();
+                response.extensions_mut().insert(RedirectHistory(visited));
+
+                return Ok(response);
+            }
+        };
+    }
+}
+
+fn authorize(request: &mut Request, authorization: ClientixResult<Option<HeaderValue>>) -> ClientixResult<()> {
+    if let Some(authorization) = authorization? {
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+    }
+
+    Ok(())
+}
+
+fn sign(clientix: &BlockingClient, request: &mut Request) -> ClientixResult<()> {
+    let signer = match &clientix.signer {
+        Some(signer) => signer,
+        None => return Ok(())
+    };
+
+    let headers = request.headers().clone();
+    let body = request.body().and_then(|body| body.as_bytes()).unwrap_or_default().to_vec();
+    let mut signable = SignableRequest::new(request.method().clone(), request.url().clone(), headers, body);
+    signer.sign(&mut signable)?;
+
+    *request.url_mut() = signable.url().clone();
+    *request.headers_mut() = signable.headers().clone();
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use bytes::Bytes;
+use reqwest::StatusCode;
 use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
-use crate::client::response::{ClientixResponse, ClientixResult};
+use crate::client::cache::CacheStatus;
+use crate::client::redirect::RedirectHistory;
+use crate::client::response::{ClientixError, ClientixResponse, ClientixResult};
 
 pub struct BlockingResponseHandler {
     result: ClientixResult<Response>
@@ -13,9 +17,20 @@ impl BlockingResponseHandler {
         BlockingResponseHandler { result }
     }
 
+    pub(crate) fn outcome(&self) -> Result<StatusCode, &ClientixError> {
+        self.result.as_ref().map(Response::status)
+    }
+
+    pub(crate) fn response_head(&self) -> Option<(StatusCode, &HeaderMap)> {
+        self.result.as_ref().ok().map(|response| (response.status(), response.headers()))
+    }
+
     pub fn text(self) -> ClientixResult<ClientixResponse<String>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -24,7 +39,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.text()?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -33,6 +48,9 @@ impl BlockingResponseHandler {
     pub fn text_with_encoding(self, encoding: &str) -> ClientixResult<ClientixResponse<String>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -41,7 +59,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.text_with_charset(encoding)?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -50,6 +68,9 @@ impl BlockingResponseHandler {
     pub fn bytes(self) -> ClientixResult<ClientixResponse<Bytes>> {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -58,7 +79,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     response.bytes()?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -67,6 +88,9 @@ impl BlockingResponseHandler {
     pub fn json<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -75,7 +99,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_json::from_str::<T>(response.text()?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -84,6 +108,9 @@ impl BlockingResponseHandler {
     pub fn xml<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -92,7 +119,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_xml_rs::from_str::<T>(response.text()?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
@@ -101,6 +128,9 @@ impl BlockingResponseHandler {
     pub fn urlencoded<T>(self) -> ClientixResult<ClientixResponse<T>> where T: DeserializeOwned + Clone {
         match self.result {
             Ok(response) => {
+                let redirects = RedirectHistory::from_extensions(response.extensions());
+                let cache_status = CacheStatus::from_extensions(response.extensions());
+
                 Ok(ClientixResponse::new(
                     response.version(),
                     response.content_length(),
@@ -109,7 +139,7 @@ impl BlockingResponseHandler {
                     response.remote_addr(),
                     response.headers().clone(),
                     serde_urlencoded::from_str::<T>(response.text()?.as_str())?
-                ))
+                ).with_redirects(redirects).with_cache_status(cache_status))
             },
             Err(error) => Err(error),
         }
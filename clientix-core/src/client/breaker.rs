@@ -0,0 +1,338 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use reqwest::StatusCode;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const DEFAULT_FAILURE_RATE: f64 = 0.5;
+const DEFAULT_MINIMUM_REQUESTS: usize = 10;
+const DEFAULT_WINDOW: usize = 20;
+const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+const DEFAULT_HALF_OPEN_PROBES: usize = 1;
+const CLIENT_CIRCUIT: &str = "client";
+
+type StateChangeCallback = dyn Fn(&CircuitEvent) + Send + Sync;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CircuitScope {
+    #[default]
+    Client,
+    Method
+}
+
+#[derive(Clone)]
+pub struct CircuitBreakerConfig {
+    failure_rate: f64,
+    minimum_requests: usize,
+    window: usize,
+    open_duration: Duration,
+    half_open_probes: usize,
+    scope: CircuitScope,
+    on_state_change: Option<Arc<StateChangeCallback>>
+}
+
+pub struct CircuitEvent<'a> {
+    circuit: &'a str,
+    from: CircuitState,
+    to: CircuitState
+}
+
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, Circuit>>
+}
+
+// a slot in the circuit taken for one request, a half-open probe slot is given back when the permit is dropped
+pub struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    circuit: String,
+    probe: bool,
+    completed: bool
+}
+
+struct Circuit {
+    state: CircuitState,
+    outcomes: VecDeque<bool>,
+    opened_at: Option<Instant>,
+    probes: usize,
+    successful_probes: usize
+}
+
+impl Default for CircuitBreakerConfig {
+
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_rate: DEFAULT_FAILURE_RATE,
+            minimum_requests: DEFAULT_MINIMUM_REQUESTS,
+            window: DEFAULT_WINDOW,
+            open_duration: DEFAULT_OPEN_DURATION,
+            half_open_probes: DEFAULT_HALF_OPEN_PROBES,
+            scope: CircuitScope::Client,
+            on_state_change: None
+        }
+    }
+
+}
+
+impl CircuitBreakerConfig {
+
+    // share of failed requests in the window that opens the circuit, from 0.0 to 1.0
+    pub fn failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = failure_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn minimum_requests(mut self, minimum_requests: usize) -> Self {
+        self.minimum_requests = minimum_requests.max(1);
+        self
+    }
+
+    // number of the latest requests the failure rate is computed over
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    pub fn open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    pub fn half_open_probes(mut self, probes: usize) -> Self {
+        self.half_open_probes = probes.max(1);
+        self
+    }
+
+    pub fn scope(mut self, scope: CircuitScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn per_method(self) -> Self {
+        self.scope(CircuitScope::Method)
+    }
+
+    pub fn on_state_change<F>(mut self, callback: F) -> Self where F: Fn(&CircuitEvent) + Send + Sync + 'static {
+        self.on_state_change = Some(Arc::new(callback));
+        self
+    }
+
+}
+
+impl Debug for CircuitBreakerConfig {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakerConfig")
+            .field("failure_rate", &self.failure_rate)
+            .field("minimum_requests", &self.minimum_requests)
+            .field("window", &self.window)
+            .field("open_duration", &self.open_duration)
+            .field("half_open_probes", &self.half_open_probes)
+            .field("scope", &self.scope)
+            .finish()
+    }
+
+}
+
+impl CircuitEvent<'_> {
+
+    // the generated method name for per-method circuits, "client" otherwise
+    pub fn circuit(&self) -> &str {
+        self.circuit
+    }
+
+    pub fn from(&self) -> CircuitState {
+        self.from
+    }
+
+    pub fn to(&self) -> CircuitState {
+        self.to
+    }
+
+}
+
+impl CircuitBreaker {
+
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreaker { config, circuits: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn circuit_name<'a>(&self, operation: &'a str) -> &'a str {
+        match self.config.scope {
+            CircuitScope::Client => CLIENT_CIRCUIT,
+            CircuitScope::Method => operation
+        }
+    }
+
+    pub fn state(&self, operation: &str) -> CircuitState {
+        let circuit = self.circuit_name(operation);
+        self.circuits.lock().ok()
+            .and_then(|circuits| circuits.get(circuit).map(|circuit| circuit.state))
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    pub fn acquire(&self, operation: &str) -> ClientixResult<CircuitPermit<'_>> {
+        let name = self.circuit_name(operation).to_string();
+        let mut circuits = self.circuits.lock().map_err(|err| breaker_error(format!("circuit breaker locked: {err:?}")))?;
+        let circuit = circuits.entry(name.clone()).or_insert_with(Circuit::new);
+        let previous = circuit.state;
+
+        if circuit.state == CircuitState::Open && circuit.opened_at.is_some_and(|opened_at| opened_at.elapsed() >= self.config.open_duration) {
+            circuit.half_open();
+        }
+
+        let permit = match circuit.state {
+            CircuitState::Closed => Ok(CircuitPermit { breaker: self, circuit: name.clone(), probe: false, completed: false }),
+            CircuitState::HalfOpen if circuit.probes < self.config.half_open_probes => {
+                circuit.probes += 1;
+                Ok(CircuitPermit { breaker: self, circuit: name.clone(), probe: true, completed: false })
+            },
+            _ => Err(ClientixError::CircuitOpen(ClientixErrorData::builder().message(format!("circuit {name} is open").as_str()).build(), None))
+        };
+
+        let current = circuit.state;
+        drop(circuits);
+        self.notify(name.as_str(), previous, current);
+
+        permit
+    }
+
+    fn complete(&self, name: &str, probe: bool, failed: Option<bool>) {
+        let (previous, current) = {
+            let mut circuits = match self.circuits.lock() {
+                Ok(circuits) => circuits,
+                Err(_) => return
+            };
+
+            let circuit = match circuits.get_mut(name) {
+                Some(circuit) => circuit,
+                None => return
+            };
+
+            let previous = circuit.state;
+            if probe && circuit.state == CircuitState::HalfOpen {
+                circuit.probes = circuit.probes.saturating_sub(1);
+            }
+
+            match (circuit.state, failed) {
+                (_, None) => (),
+                (CircuitState::HalfOpen, Some(true)) if probe => circuit.open(),
+                (CircuitState::HalfOpen, Some(false)) if probe => {
+                    circuit.successful_probes += 1;
+                    if circuit.successful_probes >= self.config.half_open_probes {
+                        circuit.close();
+                    }
+                },
+                (CircuitState::Closed, Some(failed)) => {
+                    circuit.outcomes.push_back(failed);
+                    while circuit.outcomes.len() > self.config.window {
+                        circuit.outcomes.pop_front();
+                    }
+
+                    if circuit.failure_rate(self.config.minimum_requests).is_some_and(|rate| rate >= self.config.failure_rate) {
+                        circuit.open();
+                    }
+                },
+                // late results of requests started before the circuit opened are ignored
+                _ => ()
+            }
+
+            (previous, circuit.state)
+        };
+
+        self.notify(name, previous, current);
+    }
+
+    fn notify(&self, circuit: &str, from: CircuitState, to: CircuitState) {
+        if let (Some(callback), true) = (&self.config.on_state_change, from != to) {
+            callback(&CircuitEvent { circuit, from, to });
+        }
+    }
+
+}
+
+impl Debug for CircuitBreaker {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker").field("config", &self.config).finish()
+    }
+
+}
+
+impl CircuitPermit<'_> {
+
+    // transport errors and 5xx responses count as failures, other errors say nothing about the service
+    pub fn complete(mut self, result: Result<StatusCode, &ClientixError>) {
+        let failed = match result {
+            Ok(status) => Some(status.is_server_error()),
+            Err(ClientixError::Http(_, _)) => Some(true),
+            Err(_) => None
+        };
+
+        self.completed = true;
+        self.breaker.complete(self.circuit.as_str(), self.probe, failed);
+    }
+
+}
+
+impl Drop for CircuitPermit<'_> {
+
+    fn drop(&mut self) {
+        if !self.completed {
+            self.breaker.complete(self.circuit.as_str(), self.probe, None);
+        }
+    }
+
+}
+
+impl Circuit {
+
+    fn new() -> Self {
+        Circuit {
+            state: CircuitState::Closed,
+            outcomes: VecDeque::new(),
+            opened_at: None,
+            probes: 0,
+            successful_probes: 0
+        }
+    }
+
+    fn failure_rate(&self, minimum_requests: usize) -> Option<f64> {
+        match self.outcomes.len() >= minimum_requests {
+            true => Some(self.outcomes.iter().filter(|failed| **failed).count() as f64 / self.outcomes.len() as f64),
+            false => None
+        }
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(Instant::now());
+        self.outcomes.clear();
+        self.probes = 0;
+    }
+
+    fn half_open(&mut self) {
+        self.state = CircuitState::HalfOpen;
+        self.probes = 0;
+        self.successful_probes = 0;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.opened_at = None;
+        self.outcomes.clear();
+    }
+
+}
+
+fn breaker_error(message: String) -> ClientixError {
+    ClientixError::Other(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+use crate::client::cache::{CachedResponse, ClientixCacheStorage};
+use crate::client::signer::hex_encode;
+
+// responses kept as one JSON file per url in a directory, created on the first write;
+// the cache is best effort, an unreadable or unwritable entry is treated as missing
+#[derive(Clone, Debug)]
+pub struct DiskCache {
+    directory: PathBuf
+}
+
+impl DiskCache {
+
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        DiskCache { directory: directory.as_ref().to_path_buf() }
+    }
+
+    pub fn directory(&self) -> &Path {
+        self.directory.as_path()
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", hex_encode(&Sha256::digest(key.as_bytes()))))
+    }
+
+}
+
+impl ClientixCacheStorage for DiskCache {
+
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let content = fs::read(self.path(key)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let content = match serde_json::to_vec(&response) {
+            Ok(content) => content,
+            Err(_) => return
+        };
+
+        // written aside and renamed, so a reader never sees a half written entry
+        let path = self.path(key);
+        let temporary = path.with_extension(format!("{}.tmp", rand::random::<u32>()));
+        let written = fs::create_dir_all(&self.directory)
+            .and_then(|_| fs::write(&temporary, content))
+            .and_then(|_| fs::rename(&temporary, &path));
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+
+}
@@ -0,0 +1,80 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use crate::client::cache::{CachedResponse, ClientixCacheStorage};
+
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+// responses kept in memory, the oldest stored entry is evicted once the limit is reached
+#[derive(Debug)]
+pub struct MemoryCache {
+    max_entries: usize,
+    entries: Mutex<MemoryEntries>
+}
+
+#[derive(Debug, Default)]
+struct MemoryEntries {
+    responses: HashMap<String, CachedResponse>,
+    order: VecDeque<String>
+}
+
+impl Default for MemoryCache {
+
+    fn default() -> Self {
+        MemoryCache::new()
+    }
+
+}
+
+impl MemoryCache {
+
+    pub fn new() -> Self {
+        MemoryCache { max_entries: DEFAULT_MAX_ENTRIES, entries: Default::default() }
+    }
+
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.responses.len()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+}
+
+impl ClientixCacheStorage for MemoryCache {
+
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().ok()?.responses.get(key).cloned()
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return
+        };
+
+        if entries.responses.insert(key.to_string(), response).is_some() {
+            entries.order.retain(|stored| stored != key);
+        }
+
+        entries.order.push_back(key.to_string());
+        while entries.order.len() > self.max_entries {
+            if let Some(evicted) = entries.order.pop_front() {
+                entries.responses.remove(&evicted);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.responses.remove(key);
+            entries.order.retain(|stored| stored != key);
+        }
+    }
+
+}
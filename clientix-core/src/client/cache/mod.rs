@@ -0,0 +1,399 @@
+pub mod disk;
+pub mod memory;
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bytes::Bytes;
+use http::header::{AGE, AUTHORIZATION, CACHE_CONTROL, COOKIE, CONTENT_LENGTH, DATE, ETAG, EXPIRES, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE, IF_UNMODIFIED_SINCE, LAST_MODIFIED, PROXY_AUTHORIZATION, VARY};
+use http::{Extensions, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Version};
+use reqwest::{ResponseBuilderExt, Url};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::client::redirect::RedirectHistory;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+// statuses a cache may store without explicit freshness, RFC 9110 section 15.1
+const HEURISTICALLY_CACHEABLE: [u16; 11] = [200, 203, 204, 300, 301, 308, 404, 405, 410, 414, 501];
+// share of the time since Last-Modified a response without explicit freshness is considered fresh
+const HEURISTIC_FRACTION: u32 = 10;
+
+pub trait ClientixCacheStorage: Send + Sync {
+
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    fn put(&self, key: &str, response: CachedResponse);
+
+    fn remove(&self, key: &str);
+
+}
+
+impl Debug for dyn ClientixCacheStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientixCacheStorage")
+    }
+}
+
+// how a response was obtained, Revalidated is a stale entry confirmed by a 304 and counts as a hit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Revalidated,
+    Miss
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedResponse {
+    status: u16,
+    url: String,
+    // values kept as bytes, a header value is not necessarily UTF-8
+    headers: Vec<(String, Vec<u8>)>,
+    redirects: Vec<String>,
+    vary: Vec<(String, Option<String>)>,
+    response_time: SystemTime,
+    initial_age: Duration,
+    #[serde(serialize_with = "serialize_body", deserialize_with = "deserialize_body")]
+    body: Vec<u8>
+}
+
+pub enum CacheLookup {
+    // the request is not answered from the cache nor stored in it
+    Bypass,
+    Miss,
+    Fresh(http::Response<Bytes>),
+    Stale(CachedResponse)
+}
+
+// a private cache as described by RFC 9111 in front of the storage
+#[derive(Debug)]
+pub struct HttpCache {
+    storage: Arc<dyn ClientixCacheStorage>,
+    authenticated: bool
+}
+
+impl CacheStatus {
+
+    pub fn is_hit(&self) -> bool {
+        matches!(self, CacheStatus::Hit | CacheStatus::Revalidated)
+    }
+
+    pub fn from_extensions(extensions: &Extensions) -> Option<CacheStatus> {
+        extensions.get::<CacheStatus>().copied()
+    }
+
+}
+
+impl CachedResponse {
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    pub fn headers(&self) -> HeaderMap {
+        self.headers.iter()
+            .filter_map(|(name, value)| Some((HeaderName::from_bytes(name.as_bytes()).ok()?, HeaderValue::from_bytes(value).ok()?)))
+            .fold(HeaderMap::new(), |mut headers, (name, value)| {
+                headers.append(name, value);
+                headers
+            })
+    }
+
+    // adds the validators of the stored response so the server can answer with 304
+    pub fn validate(&self, headers: &mut HeaderMap) {
+        let stored = self.headers();
+        if let Some(etag) = stored.get(ETAG) {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+
+        if let Some(last_modified) = stored.get(LAST_MODIFIED) {
+            headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    fn new(url: &Url, request_headers: &HeaderMap, response: &http::Response<Bytes>, request_time: SystemTime, response_time: SystemTime) -> Self {
+        let headers = response.headers();
+        let vary = vary_names(headers).into_iter()
+            .map(|name| {
+                let value = request_headers.get(name.as_str()).and_then(|value| value.to_str().ok()).map(str::to_string);
+                (name, value)
+            })
+            .collect();
+
+        CachedResponse {
+            status: response.status().as_u16(),
+            url: url.to_string(),
+            headers: to_pairs(headers),
+            redirects: RedirectHistory::from_extensions(response.extensions()).iter().map(Url::to_string).collect(),
+            vary,
+            response_time,
+            initial_age: initial_age(headers, request_time, response_time),
+            body: response.body().to_vec()
+        }
+    }
+
+    fn has_validator(&self) -> bool {
+        let headers = self.headers();
+        headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED)
+    }
+
+    fn matches(&self, request_headers: &HeaderMap) -> bool {
+        self.vary.iter().all(|(name, value)| {
+            request_headers.get(name.as_str()).and_then(|value| value.to_str().ok()) == value.as_deref()
+        })
+    }
+
+    // RFC 9111 section 4.2.3
+    fn current_age(&self, now: SystemTime) -> Duration {
+        self.initial_age + now.duration_since(self.response_time).unwrap_or_default()
+    }
+
+    // RFC 9111 section 4.2.1, shared cache directives are ignored by a private cache
+    fn freshness_lifetime(&self) -> Duration {
+        let headers = self.headers();
+        let directives = directives(&headers);
+
+        if let Some(max_age) = directives.get("max-age").and_then(|max_age| max_age.as_deref()).and_then(|max_age| max_age.parse::<u64>().ok()) {
+            return Duration::from_secs(max_age);
+        }
+
+        let date = header_date(&headers, DATE).unwrap_or(self.response_time);
+        if let Some(expires) = headers.get(EXPIRES) {
+            // an invalid Expires, e.g. "0", means already expired
+            return expires.to_str().ok()
+                .and_then(|expires| httpdate::parse_http_date(expires).ok())
+                .and_then(|expires| expires.duration_since(date).ok())
+                .unwrap_or_default();
+        }
+
+        match (HEURISTICALLY_CACHEABLE.contains(&self.status), header_date(&headers, LAST_MODIFIED)) {
+            (true, Some(last_modified)) => date.duration_since(last_modified).unwrap_or_default() / HEURISTIC_FRACTION,
+            _ => Duration::ZERO
+        }
+    }
+
+    fn response(&self, status: CacheStatus, now: SystemTime) -> ClientixResult<http::Response<Bytes>> {
+        let url = Url::parse(self.url.as_str()).map_err(|err| cache_error(format!("invalid cached url: {}", self.url), Some(err.into())))?;
+        let redirects = self.redirects.iter().filter_map(|url| Url::parse(url).ok()).collect::<Vec<_>>();
+        let status_code = StatusCode::from_u16(self.status).map_err(|err| cache_error(format!("invalid cached status: {}", self.status), Some(err.into())))?;
+
+        let mut builder = http::Response::builder()
+            .status(status_code)
+            .url(url)
+            .extension(RedirectHistory(redirects))
+            .extension(status);
+
+        if let Some(headers) = builder.headers_mut() {
+            *headers = self.headers();
+            headers.insert(AGE, HeaderValue::from(self.current_age(now).as_secs()));
+        }
+
+        builder.body(Bytes::from(self.body.clone())).map_err(|err| cache_error("invalid cached response".to_string(), Some(err.into())))
+    }
+
+}
+
+impl HttpCache {
+
+    pub fn new(storage: Arc<dyn ClientixCacheStorage>) -> Self {
+        HttpCache { storage, authenticated: false }
+    }
+
+    // every request of the client carries credentials, e.g. from an auth provider, an api key or a signer
+    pub fn authenticated(mut self, authenticated: bool) -> Self {
+        self.authenticated = authenticated;
+        self
+    }
+
+    pub fn lookup(&self, method: &Method, url: &Url, request_headers: &HeaderMap) -> CacheLookup {
+        let request_directives = directives(request_headers);
+
+        // a request with its own conditions expects to see the server answer, 304 included
+        let conditional = [IF_NONE_MATCH, IF_MODIFIED_SINCE, IF_MATCH, IF_UNMODIFIED_SINCE, IF_RANGE].iter()
+            .any(|header| request_headers.contains_key(header));
+
+        if *method != Method::GET || request_directives.contains_key("no-store") || conditional {
+            return CacheLookup::Bypass;
+        }
+
+        let cached = match self.storage.get(url.as_str()) {
+            Some(cached) if cached.matches(request_headers) => cached,
+            _ => return CacheLookup::Miss
+        };
+
+        let now = SystemTime::now();
+        let age = cached.current_age(now);
+        let max_age = request_directives.get("max-age")
+            .and_then(|max_age| max_age.as_deref())
+            .and_then(|max_age| max_age.parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        let response_directives = directives(&cached.headers());
+        let revalidate = request_directives.contains_key("no-cache") || response_directives.contains_key("no-cache");
+        let fresh = !revalidate && age < cached.freshness_lifetime() && max_age.is_none_or(|max_age| age <= max_age);
+
+        match (fresh, cached.has_validator()) {
+            (true, _) => match cached.response(CacheStatus::Hit, now) {
+                Ok(response) => CacheLookup::Fresh(response),
+                Err(_) => CacheLookup::Miss
+            },
+            (false, true) => CacheLookup::Stale(cached),
+            (false, false) => CacheLookup::Miss
+        }
+    }
+
+    // RFC 9111 section 3, a response without freshness or validators is useless to keep
+    pub fn storable(&self, request_headers: &HeaderMap, status: StatusCode, headers: &HeaderMap) -> bool {
+        let directives = directives(headers);
+        if directives.contains_key("no-store") || vary_names(headers).iter().any(|name| name == "*") {
+            return false;
+        }
+
+        // RFC 9111 section 3.5, the response to a request with credentials is kept only when the server marks it public,
+        // otherwise it would be served to requests with other credentials or none
+        let credentials = self.authenticated
+            || [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION].iter().any(|header| request_headers.contains_key(header))
+            || request_headers.values().any(HeaderValue::is_sensitive);
+
+        if credentials && !directives.contains_key("public") {
+            return false;
+        }
+
+        if status == StatusCode::PARTIAL_CONTENT || status == StatusCode::NOT_MODIFIED || status.is_informational() {
+            return false;
+        }
+
+        let explicit = directives.contains_key("max-age") || headers.contains_key(EXPIRES);
+        let validator = headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED);
+
+        explicit || (validator && HEURISTICALLY_CACHEABLE.contains(&status.as_u16()))
+    }
+
+    // the entry is kept under the requested url, the response url differs from it after redirects
+    pub fn store(&self, url: &Url, request_headers: &HeaderMap, response_url: &Url, response: &http::Response<Bytes>, request_time: SystemTime, response_time: SystemTime) {
+        self.storage.put(url.as_str(), CachedResponse::new(response_url, request_headers, response, request_time, response_time));
+    }
+
+    // RFC 9111 section 4.3.4, the 304 headers replace the stored ones and the entry is fresh again
+    pub fn revalidated(&self, url: &Url, mut cached: CachedResponse, headers: &HeaderMap, request_time: SystemTime, response_time: SystemTime) -> ClientixResult<http::Response<Bytes>> {
+        let mut stored = cached.headers();
+        for name in headers.keys().filter(|name| *name != CONTENT_LENGTH) {
+            stored.remove(name);
+            for value in headers.get_all(name) {
+                stored.append(name, value.clone());
+            }
+        }
+
+        cached.headers = to_pairs(&stored);
+        cached.response_time = response_time;
+        cached.initial_age = initial_age(headers, request_time, response_time);
+        self.storage.put(url.as_str(), cached.clone());
+
+        cached.response(CacheStatus::Revalidated, response_time)
+    }
+
+    pub fn forget(&self, url: &Url) {
+        self.storage.remove(url.as_str());
+    }
+
+    // the network response read into memory so it can be stored and still handed to the caller
+    pub fn buffered(status: StatusCode, version: Version, url: &Url, headers: &HeaderMap, extensions: &Extensions, body: Bytes) -> ClientixResult<http::Response<Bytes>> {
+        let mut builder = http::Response::builder()
+            .status(status)
+            .version(version)
+            .url(url.clone());
+
+        if let Some(stored) = builder.headers_mut() {
+            stored.extend(headers.clone());
+        }
+
+        if let Some(stored) = builder.extensions_mut() {
+            stored.extend(extensions.clone());
+            stored.insert(CacheStatus::Miss);
+        }
+
+        builder.body(body).map_err(|err| cache_error("invalid response".to_string(), Some(err.into())))
+    }
+
+    // RFC 9111 section 4.4, a successful unsafe request makes the stored response of its url obsolete
+    pub fn invalidate(&self, method: &Method, url: &Url, status: StatusCode) {
+        if !method.is_safe() && (status.is_success() || status.is_redirection()) {
+            self.storage.remove(url.as_str());
+        }
+    }
+
+}
+
+fn directives(headers: &HeaderMap) -> HashMap<String, Option<String>> {
+    headers.get_all(CACHE_CONTROL).iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|directive| {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name, Some(value.trim().trim_matches('"').to_string())),
+                None => (directive, None)
+            };
+
+            let name = name.trim().to_ascii_lowercase();
+            (!name.is_empty()).then_some((name, value))
+        })
+        .collect()
+}
+
+fn vary_names(headers: &HeaderMap) -> Vec<String> {
+    headers.get_all(VARY).iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn header_date(headers: &HeaderMap, name: HeaderName) -> Option<SystemTime> {
+    headers.get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok())
+}
+
+fn initial_age(headers: &HeaderMap, request_time: SystemTime, response_time: SystemTime) -> Duration {
+    let age = headers.get(AGE)
+        .and_then(|age| age.to_str().ok())
+        .and_then(|age| age.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_default();
+
+    let apparent_age = header_date(headers, DATE)
+        .and_then(|date| response_time.duration_since(date).ok())
+        .unwrap_or_default();
+
+    let response_delay = response_time.duration_since(request_time).unwrap_or_default();
+
+    apparent_age.max(age + response_delay)
+}
+
+fn to_pairs(headers: &HeaderMap) -> Vec<(String, Vec<u8>)> {
+    headers.iter()
+        .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+        .collect()
+}
+
+fn serialize_body<S>(body: &[u8], serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+    serializer.serialize_str(BASE64_STANDARD.encode(body).as_str())
+}
+
+fn deserialize_body<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error> where D: Deserializer<'de> {
+    BASE64_STANDARD.decode(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+fn cache_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Other(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
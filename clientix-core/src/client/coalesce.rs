@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use bytes::Bytes;
+use futures_util::future::{BoxFuture, Shared};
+use futures_util::FutureExt;
+use http::header::{AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION};
+use http::{Extensions, HeaderMap, Method};
+use reqwest::{Body, Response, ResponseBuilderExt, StatusCode, Url, Version};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+type SharedResult = Result<Arc<BufferedResponse>, Arc<ClientixError>>;
+type SharedCall = Shared<BoxFuture<'static, SharedResult>>;
+
+// identical GET requests in flight at the same time share one network call
+#[derive(Debug, Default)]
+pub struct Coalescing {
+    vary: Vec<String>,
+    credentials: Vec<String>,
+    calls: Mutex<HashMap<String, SharedCall>>
+}
+
+// the response read into memory once, every caller gets its own copy
+#[derive(Debug)]
+struct BufferedResponse {
+    status: StatusCode,
+    version: Version,
+    url: Url,
+    headers: HeaderMap,
+    extensions: Extensions,
+    body: Bytes
+}
+
+impl Coalescing {
+
+    // requests differing only in one of the vary headers are not coalesced
+    pub fn new(vary: &[String]) -> Self {
+        let credentials = [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION].iter().map(|name| name.to_string()).collect();
+        Coalescing { vary: vary.iter().map(|name| name.to_ascii_lowercase()).collect(), credentials, calls: Default::default() }
+    }
+
+    // a header carrying credentials, e.g. the api key header, always part of the identity like Authorization
+    pub fn credential(mut self, name: &str) -> Self {
+        self.credentials.push(name.to_ascii_lowercase());
+        self
+    }
+
+    pub fn key(&self, operation: &str, method: &Method, url: &str, queries: &[(String, String)], headers: &HeaderMap) -> String {
+        let mut key = format!("{operation} {method} {url}");
+        for (name, value) in queries {
+            key.push_str(format!(" {name}={value}").as_str());
+        }
+
+        // credentials and sensitive headers tell callers apart whatever the vary list says,
+        // a caller never gets the response fetched with the credentials of another one
+        let mut identity = headers.iter()
+            .filter(|(name, value)| value.is_sensitive() || self.vary.iter().chain(self.credentials.iter()).any(|header| header == name.as_str()))
+            .map(|(name, value)| format!(" {name}: {}", String::from_utf8_lossy(value.as_bytes())))
+            .collect::<Vec<_>>();
+        identity.sort();
+
+        for header in identity {
+            key.push_str(header.as_str());
+        }
+
+        key
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.calls.lock().map(|calls| calls.len()).unwrap_or_default()
+    }
+
+    // the first caller starts the call, the ones arriving while it is in flight wait for its result
+    pub async fn share<F>(&self, key: String, call: F) -> ClientixResult<Response> where F: Future<Output = ClientixResult<Response>> + Send + 'static {
+        let shared = {
+            let mut calls = self.calls.lock().map_err(|err| coalesce_error(format!("coalescing locked: {err:?}")))?;
+            calls.entry(key.clone())
+                .or_insert_with(|| call.then(BufferedResponse::read).boxed().shared())
+                .clone()
+        };
+
+        let result = shared.clone().await;
+        if let Ok(mut calls) = self.calls.lock() {
+            if calls.get(&key).is_some_and(|call| call.ptr_eq(&shared)) {
+                calls.remove(&key);
+            }
+        }
+
+        match result {
+            Ok(response) => response.response(),
+            Err(error) => Err(copy_error(&error))
+        }
+    }
+
+}
+
+impl BufferedResponse {
+
+    async fn read(result: ClientixResult<Response>) -> SharedResult {
+        let response = result.map_err(Arc::new)?;
+        let status = response.status();
+        let version = response.version();
+        let url = response.url().clone();
+        let headers = response.headers().clone();
+        let extensions = response.extensions().clone();
+        let body = response.bytes().await.map_err(|err| Arc::new(err.into()))?;
+
+        Ok(Arc::new(BufferedResponse { status, version, url, headers, extensions, body }))
+    }
+
+    fn response(&self) -> ClientixResult<Response> {
+        let mut builder = http::Response::builder()
+            .status(self.status)
+            .version(self.version)
+            .url(self.url.clone());
+
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers.clone());
+        }
+
+        if let Some(extensions) = builder.extensions_mut() {
+            extensions.extend(self.extensions.clone());
+        }
+
+        builder.body(Body::from(self.body.clone()))
+            .map(Response::from)
+            .map_err(|err| ClientixError::Other(ClientixErrorData::new(), Some(err.into())))
+    }
+
+}
+
+// errors are not Clone, each caller gets the same kind of error with the source rendered as text
+fn copy_error(error: &ClientixError) -> ClientixError {
+    let (data, source) = match error {
+        ClientixError::Http(data, source) |
+        ClientixError::IO(data, source) |
+        ClientixError::InvalidRequest(data, source) |
+        ClientixError::Auth(data, source) |
+        ClientixError::Config(data, source) |
+        ClientixError::InvalidResponse(data, source) |
+        ClientixError::CircuitOpen(data, source) |
+        ClientixError::RateLimited(data, source) |
+        ClientixError::Other(data, source) => (data, source)
+    };
+
+    let data = match data.message() {
+        Some(message) => ClientixErrorData::builder().message(message.as_str()).build(),
+        None => ClientixErrorData::new()
+    };
+    let source = source.as_ref().map(|source| source.to_string().into());
+
+    match error {
+        ClientixError::Http(_, _) => ClientixError::Http(data, source),
+        ClientixError::IO(_, _) => ClientixError::IO(data, source),
+        ClientixError::InvalidRequest(_, _) => ClientixError::InvalidRequest(data, source),
+        ClientixError::Auth(_, _) => ClientixError::Auth(data, source),
+        ClientixError::Config(_, _) => ClientixError::Config(data, source),
+        ClientixError::InvalidResponse(_, _) => ClientixError::InvalidResponse(data, source),
+        ClientixError::CircuitOpen(_, _) => ClientixError::CircuitOpen(data, source),
+        ClientixError::RateLimited(_, _) => ClientixError::RateLimited(data, source),
+        ClientixError::Other(_, _) => ClientixError::Other(data, source)
+    }
+}
+
+fn coalesce_error(message: String) -> ClientixError {
+    ClientixError::Other(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
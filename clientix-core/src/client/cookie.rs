@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::RwLock;
+use cookie_store::CookieStore;
+use http::header::COOKIE;
+use http::{HeaderMap, HeaderValue};
+use reqwest::Url;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+#[derive(Default)]
+pub struct ClientixCookieJar {
+    store: RwLock<CookieStore>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientixCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: String
+}
+
+impl ClientixCookieJar {
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> ClientixResult<ClientixCookieJar> {
+        let file = File::open(path.as_ref())
+            .map_err(|err| ClientixError::IO(ClientixErrorData::new(), Some(err.into())))?;
+
+        let store = cookie_store::serde::json::load_all(BufReader::new(file))
+            .map_err(|err| cookie_error(format!("invalid cookie file: {}", path.as_ref().display()), Some(err)))?;
+
+        Ok(ClientixCookieJar { store: RwLock::new(store) })
+    }
+
+    // session cookies are kept as well, a restored jar continues the same session; expired ones are dropped
+    pub fn save(&self, path: impl AsRef<Path>) -> ClientixResult<()> {
+        let store = self.store.read().map_err(|err| cookie_error(format!("cookie jar poisoned: {err}"), None))?;
+        let unexpired = CookieStore::from_cookies(store.iter_unexpired().cloned().map(Ok::<_, ()>), false)
+            .unwrap_or_default();
+
+        let file = File::create(path.as_ref())
+            .map_err(|err| ClientixError::IO(ClientixErrorData::new(), Some(err.into())))?;
+
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(&unexpired, &mut BufWriter::new(file))
+            .map_err(|err| cookie_error(format!("failed to save cookies: {}", path.as_ref().display()), Some(err)))
+    }
+
+    pub fn add(&self, cookie: &str, url: &str) -> ClientixResult<()> {
+        let url = Url::parse(url).map_err(|err| cookie_error(format!("invalid cookie url: {url}"), Some(err.into())))?;
+        let mut store = self.store.write().map_err(|err| cookie_error(format!("cookie jar poisoned: {err}"), None))?;
+
+        store.parse(cookie, &url)
+            .map(|_| ())
+            .map_err(|err| cookie_error(format!("invalid cookie: {err}"), None))
+    }
+
+    pub fn cookies(&self) -> Vec<ClientixCookie> {
+        match self.store.read() {
+            Ok(store) => store.iter_unexpired().map(to_cookie).collect(),
+            Err(_) => vec![]
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<ClientixCookie> {
+        self.cookies().into_iter().find(|cookie| cookie.name == name)
+    }
+
+    pub fn remove(&self, domain: &str, path: &str, name: &str) -> bool {
+        match self.store.write() {
+            Ok(mut store) => store.remove(domain, path, name).is_some(),
+            Err(_) => false
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut store) = self.store.write() {
+            store.clear();
+        }
+    }
+
+    pub fn header(&self, url: &Url) -> Option<String> {
+        let store = self.store.read().ok()?;
+        let header = store.get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if header.is_empty() { None } else { Some(header) }
+    }
+
+    // reqwest skips the jar when a request already carries a Cookie header, so explicit cookies are merged here
+    pub fn apply(&self, url: &Url, headers: &mut HeaderMap) {
+        let cookies = match self.header(url) {
+            Some(cookies) => cookies,
+            None => return
+        };
+
+        let cookies = match headers.get(COOKIE).and_then(|value| value.to_str().ok()) {
+            Some(explicit) => format!("{cookies}; {explicit}"),
+            None => cookies
+        };
+
+        if let Ok(mut value) = HeaderValue::from_str(cookies.as_str()) {
+            value.set_sensitive(true);
+            headers.insert(COOKIE, value);
+        }
+    }
+
+}
+
+impl reqwest::cookie::CookieStore for ClientixCookieJar {
+
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| cookie::Cookie::parse(value.to_string()).ok())
+            .collect::<Vec<_>>();
+
+        if let Ok(mut store) = self.store.write() {
+            store.store_response_cookies(cookies.into_iter(), url);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.header(url).and_then(|header| HeaderValue::from_str(header.as_str()).ok())
+    }
+
+}
+
+// bytes a cookie value may not hold as they are, e.g. ; and , which would split the Cookie header, are percent-encoded
+pub fn encode_cookie_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            0x21 | 0x23..=0x24 | 0x26..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E => encoded.push(byte as char),
+            _ => encoded.push_str(format!("%{byte:02X}").as_str())
+        }
+    }
+
+    encoded
+}
+
+fn to_cookie(cookie: &cookie_store::Cookie<'static>) -> ClientixCookie {
+    ClientixCookie {
+        name: cookie.name().to_string(),
+        value: cookie.value().to_string(),
+        domain: cookie.domain.as_cow().map(|domain| domain.to_string()),
+        path: cookie.path.as_ref().to_string()
+    }
+}
+
+fn cookie_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Other(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::duration::parse_duration;
+
+const LATENCY_WINDOW: usize = 100;
+// a percentile of a handful of requests says nothing, no hedging until there are enough samples
+const MINIMUM_SAMPLES: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HedgePolicy {
+    Fixed(Duration),
+    Percentile(u8)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HedgeMetrics {
+    requests: u64,
+    fired: u64,
+    won: u64
+}
+
+// hedging state of an async client, latencies and counters are kept per generated method
+#[derive(Debug, Default)]
+pub struct Hedging {
+    operations: Mutex<HashMap<String, HedgeStats>>
+}
+
+#[derive(Debug, Default)]
+struct HedgeStats {
+    latencies: VecDeque<Duration>,
+    metrics: HedgeMetrics
+}
+
+impl TryFrom<&str> for HedgePolicy {
+    type Error = ClientixError;
+
+    // "50ms" waits a fixed delay, "p95" waits for the 95th percentile of the latencies seen so far
+    fn try_from(value: &str) -> ClientixResult<Self> {
+        let value = value.trim();
+        let invalid = || hedge_error(format!("invalid hedge: {value}, expected a delay like 50ms or a percentile like p95"));
+
+        match value.strip_prefix('p') {
+            Some(percentile) => match percentile.parse::<u8>() {
+                Ok(percentile) if percentile > 0 && percentile < 100 => Ok(HedgePolicy::Percentile(percentile)),
+                _ => Err(invalid())
+            },
+            None => parse_duration(value).map(HedgePolicy::Fixed).map_err(|_| invalid())
+        }
+    }
+}
+
+impl HedgeMetrics {
+
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    // hedges sent because the first request took longer than the delay
+    pub fn fired(&self) -> u64 {
+        self.fired
+    }
+
+    // hedges that answered before the first request
+    pub fn won(&self) -> u64 {
+        self.won
+    }
+
+}
+
+impl Hedging {
+
+    pub fn delay(&self, operation: &str, policy: HedgePolicy) -> Option<Duration> {
+        match policy {
+            HedgePolicy::Fixed(delay) => Some(delay),
+            HedgePolicy::Percentile(percentile) => {
+                let operations = self.operations.lock().ok()?;
+                let stats = operations.get(operation)?;
+                if stats.latencies.len() < MINIMUM_SAMPLES {
+                    return None;
+                }
+
+                let mut latencies = stats.latencies.iter().copied().collect::<Vec<_>>();
+                latencies.sort();
+
+                let index = (latencies.len() * percentile as usize).div_ceil(100).saturating_sub(1);
+                latencies.get(index).copied()
+            }
+        }
+    }
+
+    pub fn record(&self, operation: &str, latency: Duration, fired: bool, won: bool) {
+        if let Ok(mut operations) = self.operations.lock() {
+            let stats = operations.entry(operation.to_string()).or_default();
+
+            stats.latencies.push_back(latency);
+            while stats.latencies.len() > LATENCY_WINDOW {
+                stats.latencies.pop_front();
+            }
+
+            stats.metrics.requests += 1;
+            stats.metrics.fired += fired as u64;
+            stats.metrics.won += won as u64;
+        }
+    }
+
+    pub fn metrics(&self, operation: &str) -> HedgeMetrics {
+        self.operations.lock().ok()
+            .and_then(|operations| operations.get(operation).map(|stats| stats.metrics))
+            .unwrap_or_default()
+    }
+
+}
+
+fn hedge_error(message: String) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use http::header::RETRY_AFTER;
+use http::HeaderMap;
+use reqwest::StatusCode;
+use tokio::sync::Notify;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::duration::parse_duration;
+
+const RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LimitMode {
+    #[default]
+    Wait,
+    Fail
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    rate: Option<(u32, Duration)>,
+    max_in_flight: Option<usize>,
+    mode: LimitMode
+}
+
+// the client limit and the per-method overrides, each with its own bucket and in-flight slots
+#[derive(Debug, Default)]
+pub struct RateLimits {
+    client: Option<Arc<Limit>>,
+    methods: Mutex<HashMap<String, Arc<Limit>>>
+}
+
+// the bucket and in-flight slots are shared with the views of the limit taken in another mode
+#[derive(Debug)]
+pub struct Limit {
+    mode: LimitMode,
+    bucket: Arc<Mutex<Bucket>>,
+    in_flight: Option<Arc<InFlight>>
+}
+
+pub struct LimitSlot<'a> {
+    limit: &'a Limit
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: Option<(u32, Duration)>,
+    tokens: f64,
+    refilled_at: Instant,
+    blocked_until: Option<Instant>
+}
+
+#[derive(Debug)]
+struct InFlight {
+    max: usize,
+    current: Mutex<usize>,
+    released: Condvar,
+    notify: Notify
+}
+
+impl TryFrom<String> for LimitMode {
+    type Error = ();
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "wait" => Ok(LimitMode::Wait),
+            "fail" => Ok(LimitMode::Fail),
+            _ => Err(())
+        }
+    }
+}
+
+impl RateLimitConfig {
+
+    pub fn new() -> Self {
+        RateLimitConfig::default()
+    }
+
+    pub fn rate(mut self, requests: u32, per: Duration) -> Self {
+        self.rate = Some((requests.max(1), per));
+        self
+    }
+
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max.max(1));
+        self
+    }
+
+    pub fn mode(mut self, mode: LimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn fail_fast(self) -> Self {
+        self.mode(LimitMode::Fail)
+    }
+
+    // "10/s", "100/m" or "5/500ms"
+    pub fn parse_rate(value: &str) -> ClientixResult<(u32, Duration)> {
+        let invalid = || limit_error(format!("invalid rate: {value}, expected e.g. 10/s, 100/m, 5/500ms"));
+        let (requests, per) = value.split_once('/').ok_or_else(invalid)?;
+        let requests = requests.trim().parse::<u32>().map_err(|_| invalid())?;
+        let per = per.trim();
+
+        let per = match per.starts_with(|char: char| char.is_ascii_digit()) {
+            true => parse_duration(per),
+            false => parse_duration(format!("1{per}").as_str())
+        }.map_err(|_| invalid())?;
+
+        match requests > 0 && !per.is_zero() {
+            true => Ok((requests, per)),
+            false => Err(invalid())
+        }
+    }
+
+}
+
+impl RateLimits {
+
+    pub fn new(config: Option<&RateLimitConfig>) -> Self {
+        RateLimits { client: config.map(|config| Arc::new(Limit::new(config))), methods: Default::default() }
+    }
+
+    // a method with its own rate or in-flight cap is not counted against the client limit,
+    // one overriding only the mode shares the client limit and waits or fails as it says
+    pub fn resolve(&self, operation: &str, config: Option<&RateLimitConfig>) -> Option<Arc<Limit>> {
+        let config = match config {
+            Some(config) => config,
+            None => return self.client.clone()
+        };
+
+        let own = config.rate.is_some() || config.max_in_flight.is_some();
+        if !own && self.client.as_ref().is_none_or(|client| client.mode == config.mode) {
+            return self.client.clone();
+        }
+
+        let mut methods = self.methods.lock().ok()?;
+        let limit = methods.entry(operation.to_string()).or_insert_with(|| match (own, &self.client) {
+            (false, Some(client)) => Arc::new(client.with_mode(config.mode)),
+            _ => Arc::new(Limit::new(config))
+        });
+
+        Some(limit.clone())
+    }
+
+}
+
+impl Limit {
+
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Limit {
+            mode: config.mode,
+            bucket: Arc::new(Mutex::new(Bucket::new(config.rate))),
+            in_flight: config.max_in_flight.map(|max| Arc::new(InFlight::new(max)))
+        }
+    }
+
+    pub fn with_mode(&self, mode: LimitMode) -> Self {
+        Limit { mode, bucket: self.bucket.clone(), in_flight: self.in_flight.clone() }
+    }
+
+    // the in-flight slot is taken first, so a request refused by it does not spend a token
+    pub async fn acquire(&self) -> ClientixResult<LimitSlot<'_>> {
+        if let Some(in_flight) = &self.in_flight {
+            match self.mode {
+                LimitMode::Wait => in_flight.enter().await,
+                LimitMode::Fail => in_flight.try_enter_or_fail()?
+            }
+        }
+
+        let slot = LimitSlot { limit: self };
+        while let Some(wait) = self.take_token()? {
+            tokio::time::sleep(wait).await;
+        }
+
+        Ok(slot)
+    }
+
+    pub fn acquire_blocking(&self) -> ClientixResult<LimitSlot<'_>> {
+        if let Some(in_flight) = &self.in_flight {
+            match self.mode {
+                LimitMode::Wait => in_flight.enter_blocking(),
+                LimitMode::Fail => in_flight.try_enter_or_fail()?
+            }
+        }
+
+        let slot = LimitSlot { limit: self };
+        while let Some(wait) = self.take_token()? {
+            std::thread::sleep(wait);
+        }
+
+        Ok(slot)
+    }
+
+    // the server knows the quota better, its remaining budget and Retry-After on 429 override the local bucket
+    pub fn observe(&self, status: StatusCode, headers: &HeaderMap) {
+        let mut bucket = match self.bucket.lock() {
+            Ok(bucket) => bucket,
+            Err(_) => return
+        };
+
+        let remaining = headers.get(RATE_LIMIT_REMAINING)
+            .and_then(|remaining| remaining.to_str().ok())
+            .and_then(|remaining| remaining.trim().parse::<f64>().ok());
+
+        if let Some(remaining) = remaining {
+            bucket.tokens = bucket.tokens.min(remaining);
+        }
+
+        // without a local rate the tokens are never counted, an exhausted budget pauses the requests like a 429 instead
+        let exhausted = bucket.rate.is_none() && remaining.is_some_and(|remaining| remaining < 1.0);
+        if status == StatusCode::TOO_MANY_REQUESTS || exhausted {
+            let retry_after = headers.get(RETRY_AFTER)
+                .and_then(|retry_after| retry_after.to_str().ok())
+                .and_then(parse_retry_after)
+                .unwrap_or(DEFAULT_RETRY_AFTER);
+
+            let blocked_until = Instant::now() + retry_after;
+            bucket.blocked_until = Some(bucket.blocked_until.map_or(blocked_until, |current| current.max(blocked_until)));
+        }
+    }
+
+    fn take_token(&self) -> ClientixResult<Option<Duration>> {
+        let wait = self.bucket.lock()
+            .map_err(|err| limit_error(format!("rate limiter locked: {err:?}")))?
+            .take(Instant::now());
+
+        match (wait, self.mode) {
+            (Some(wait), LimitMode::Fail) => Err(rate_limited(format!("rate limit exceeded, retry in {}ms", wait.as_millis()))),
+            (wait, _) => Ok(wait)
+        }
+    }
+
+}
+
+impl Drop for LimitSlot<'_> {
+
+    fn drop(&mut self) {
+        if let Some(in_flight) = &self.limit.in_flight {
+            in_flight.leave();
+        }
+    }
+
+}
+
+impl Bucket {
+
+    fn new(rate: Option<(u32, Duration)>) -> Self {
+        Bucket {
+            rate,
+            tokens: rate.map(|(requests, _)| requests as f64).unwrap_or(f64::MAX),
+            refilled_at: Instant::now(),
+            blocked_until: None
+        }
+    }
+
+    // takes a token or tells how long to wait for the next one
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(blocked_until) = self.blocked_until {
+            if blocked_until > now {
+                return Some(blocked_until - now);
+            }
+
+            self.blocked_until = None;
+        }
+
+        let (requests, per) = self.rate?;
+
+        let refill = requests as f64 / per.as_secs_f64();
+        self.tokens = (self.tokens + now.duration_since(self.refilled_at).as_secs_f64() * refill).min(requests as f64);
+        self.refilled_at = now;
+
+        match self.tokens >= 1.0 {
+            true => {
+                self.tokens -= 1.0;
+                None
+            },
+            false => Some(Duration::from_secs_f64((1.0 - self.tokens) / refill))
+        }
+    }
+
+}
+
+impl InFlight {
+
+    fn new(max: usize) -> Self {
+        InFlight { max, current: Mutex::new(0), released: Condvar::new(), notify: Notify::new() }
+    }
+
+    fn try_enter(&self) -> bool {
+        match self.current.lock() {
+            Ok(mut current) if *current < self.max => {
+                *current += 1;
+                true
+            },
+            _ => false
+        }
+    }
+
+    fn try_enter_or_fail(&self) -> ClientixResult<()> {
+        match self.try_enter() {
+            true => Ok(()),
+            false => Err(rate_limited(format!("too many requests in flight, the limit is {}", self.max)))
+        }
+    }
+
+    async fn enter(&self) {
+        loop {
+            let released = self.notify.notified();
+            if self.try_enter() {
+                return;
+            }
+
+            released.await;
+        }
+    }
+
+    fn enter_blocking(&self) {
+        let mut current = match self.current.lock() {
+            Ok(current) => current,
+            Err(_) => return
+        };
+
+        while *current >= self.max {
+            current = match self.released.wait(current) {
+                Ok(current) => current,
+                Err(_) => return
+            };
+        }
+
+        *current += 1;
+    }
+
+    fn leave(&self) {
+        if let Ok(mut current) = self.current.lock() {
+            *current = current.saturating_sub(1);
+        }
+
+        self.released.notify_one();
+        self.notify.notify_one();
+    }
+
+}
+
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    match value.trim().parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value.trim()).ok()
+            .map(|date| date.duration_since(SystemTime::now()).unwrap_or_default())
+    }
+}
+
+fn rate_limited(message: String) -> ClientixError {
+    ClientixError::RateLimited(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
+
+fn limit_error(message: String) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
@@ -1,16 +1,51 @@
 pub mod asynchronous;
+pub mod auth;
+pub mod balancer;
+pub mod breaker;
+pub mod cache;
+pub mod coalesce;
 pub mod blocking;
+pub mod cookie;
+pub mod hedge;
+pub mod limiter;
+pub mod pagination;
 pub mod response;
 pub mod request;
+pub mod properties;
+pub mod proxy;
+pub mod redirect;
+pub mod resolver;
+pub mod signer;
+pub mod tls;
 
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use http::header::AUTHORIZATION;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use crate::client::asynchronous::client::AsyncClient;
+use crate::client::auth::ClientixAuthProvider;
+use crate::client::auth::api_key::{ApiKey, ApiKeyLocation};
+use crate::client::auth::digest::DigestAuthProvider;
+use crate::client::auth::oauth2::OAuth2Provider;
+use crate::client::balancer::{BalanceStrategy, EjectionConfig, LoadBalancer};
+use crate::client::breaker::CircuitBreakerConfig;
+use crate::client::cache::ClientixCacheStorage;
 use crate::client::blocking::client::BlockingClient;
+use crate::client::cookie::ClientixCookieJar;
+use crate::client::limiter::RateLimitConfig;
+use crate::client::proxy::ClientixProxy;
+use crate::client::redirect::RedirectPolicy;
+use crate::client::resolver::ClientixResolver;
+use crate::client::signer::ClientixSigner;
+use crate::client::tls::TlsConfig;
+use crate::client::properties::{AuthProperties, ClientixProperties, ProxyProperties};
+use crate::client::response::ClientixResult;
+use crate::core::duration::parse_duration;
 
 pub struct Clientix {
     config: ClientConfig
@@ -20,16 +55,104 @@ pub struct ClientixBuilder {
     config: ClientConfig
 }
 
+#[cfg(unix)]
+const UNIX_SCHEME: &str = "unix://";
+// requests over a unix socket still need an http url, the host only ends up in the Host header
+#[cfg(unix)]
+const UNIX_SOCKET_URL: &str = "http://localhost";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1Only,
+    Http2PriorKnowledge
+}
+
 #[derive(Clone)]
 pub struct ClientConfig {
     url: Option<String>,
+    urls: Vec<String>,
+    load_balancing: BalanceStrategy,
+    ejection: EjectionConfig,
     path: Option<String>,
     user_agent: Option<String>,
     headers: HeaderMap,
     timeout: Option<Duration>,
     read_timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
-    connection_verbose: bool
+    connection_verbose: bool,
+    http_version: HttpVersion,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: bool,
+    local_address: Option<IpAddr>,
+    #[cfg(unix)]
+    unix_socket: Option<std::path::PathBuf>,
+    resolve_overrides: Vec<(String, Vec<SocketAddr>)>,
+    resolver: Option<Arc<dyn ClientixResolver>>,
+    gzip: bool,
+    brotli: bool,
+    deflate: bool,
+    zstd: bool,
+    auth_provider: Option<Arc<dyn ClientixAuthProvider>>,
+    signer: Option<Arc<dyn ClientixSigner>>,
+    api_key: Option<ApiKey>,
+    cookie_jar: Option<Arc<ClientixCookieJar>>,
+    tls: TlsConfig,
+    proxies: Vec<ClientixProxy>,
+    no_proxy: Option<String>,
+    env_proxy: bool,
+    redirect: RedirectPolicy,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    rate_limit: Option<RateLimitConfig>,
+    coalesce: Option<Vec<String>>,
+    cache: Option<Arc<dyn ClientixCacheStorage>>
+}
+
+impl ClientConfig {
+
+    pub fn base_url(&self) -> Option<String> {
+        #[cfg(unix)]
+        if self.url.is_none() && self.unix_socket.is_some() {
+            return Some(UNIX_SOCKET_URL.to_string());
+        }
+
+        self.url.clone()
+    }
+
+    #[cfg(unix)]
+    pub fn unix_socket(&self) -> Option<&Path> {
+        self.unix_socket.as_deref()
+    }
+
+    // a unix:// url names a socket; other platforms have none, there it stays a url that requests fail on
+    fn set_url(&mut self, url: &str) {
+        self.urls.clear();
+
+        #[cfg(unix)]
+        if let Some(socket) = url.strip_prefix(UNIX_SCHEME) {
+            self.url = None;
+            self.unix_socket = Some(std::path::PathBuf::from(socket));
+            return;
+        }
+
+        self.url = Some(url.to_string());
+    }
+
+    // a single url is reached directly, the balancer only kicks in with several replicas
+    pub fn load_balancer(&self) -> ClientixResult<Option<LoadBalancer>> {
+        match self.urls.len() > 1 {
+            true => LoadBalancer::new(&self.urls, self.load_balancing, self.ejection).map(Some),
+            false => Ok(None)
+        }
+    }
+
+    pub fn cookie_jar(&self) -> Option<Arc<ClientixCookieJar>> {
+        self.cookie_jar.clone()
+    }
+
 }
 
 impl Clientix {
@@ -43,7 +166,7 @@ impl Clientix {
     }
 
     pub fn set_url(&mut self, url: &str) {
-        self.config.url = Some(url.to_string());
+        self.config.set_url(url);
     }
 
     pub fn set_path(&mut self, path: &str) {
@@ -75,11 +198,19 @@ impl Clientix {
     }
 
     pub fn blocking(&self) -> BlockingClient {
-        BlockingClient::from(self.config.clone())
+        self.try_blocking().expect("invalid client configuration")
     }
 
     pub fn asynchronous(&self) -> AsyncClient {
-        AsyncClient::from(self.config.clone())
+        self.try_asynchronous().expect("invalid client configuration")
+    }
+
+    pub fn try_blocking(&self) -> ClientixResult<BlockingClient> {
+        BlockingClient::try_from(self.config.clone())
+    }
+
+    pub fn try_asynchronous(&self) -> ClientixResult<AsyncClient> {
+        AsyncClient::try_from(self.config.clone())
     }
 
 }
@@ -90,6 +221,9 @@ impl ClientixBuilder {
         ClientixBuilder {
             config: ClientConfig {
                 url: None,
+                urls: vec![],
+                load_balancing: Default::default(),
+                ejection: Default::default(),
                 path: None,
                 user_agent: None,
                 headers: Default::default(),
@@ -97,13 +231,162 @@ impl ClientixBuilder {
                 read_timeout: None,
                 connect_timeout: None,
                 connection_verbose: false,
+                http_version: HttpVersion::Auto,
+                pool_idle_timeout: None,
+                pool_max_idle_per_host: None,
+                tcp_keepalive: None,
+                tcp_nodelay: true,
+                local_address: None,
+                #[cfg(unix)]
+                unix_socket: None,
+                resolve_overrides: vec![],
+                resolver: None,
+                gzip: true,
+                brotli: true,
+                deflate: true,
+                zstd: true,
+                auth_provider: None,
+                signer: None,
+                api_key: None,
+                cookie_jar: None,
+                tls: Default::default(),
+                proxies: vec![],
+                no_proxy: None,
+                env_proxy: true,
+                redirect: Default::default(),
+                circuit_breaker: None,
+                rate_limit: None,
+                coalesce: None,
+                cache: None,
             },
         }
     }
 
+    pub fn from_config(path: impl AsRef<Path>, name: &str) -> ClientixResult<ClientixBuilder> {
+        ClientixBuilder::new().properties(ClientixProperties::load(path, name)?)
+    }
+
+    pub fn properties(mut self, properties: ClientixProperties) -> ClientixResult<ClientixBuilder> {
+        if let Some(url) = properties.url {
+            self = self.url(url.as_str());
+        }
+
+        if !properties.urls.is_empty() {
+            self = self.urls(&properties.urls.iter().map(String::as_str).collect::<Vec<_>>());
+        }
+
+        if let Some(strategy) = properties.load_balancing {
+            self = self.load_balancing(strategy);
+        }
+
+        if let Some(path) = properties.path {
+            self = self.path(path.as_str());
+        }
+
+        if let Some(user_agent) = properties.user_agent {
+            self = self.user_agent(user_agent.as_str());
+        }
+
+        self = self.headers(properties.headers);
+
+        if let Some(timeout) = properties.timeout {
+            self = self.timeout(parse_duration(timeout.as_str())?);
+        }
+
+        if let Some(read_timeout) = properties.read_timeout {
+            self = self.read_timeout(parse_duration(read_timeout.as_str())?);
+        }
+
+        if let Some(connect_timeout) = properties.connect_timeout {
+            self = self.connect_timeout(parse_duration(connect_timeout.as_str())?);
+        }
+
+        if let Some(connection_verbose) = properties.connection_verbose {
+            self = self.connection_verbose(connection_verbose);
+        }
+
+        self = match properties.auth {
+            Some(AuthProperties::Basic { username, password }) => self.basic_auth(username.as_str(), password.as_str()),
+            Some(AuthProperties::Bearer { token }) => self.bearer_auth(token.as_str()),
+            Some(AuthProperties::Digest { username, password }) => self.digest_auth(username.as_str(), password.as_str()),
+            Some(AuthProperties::ApiKey { location, name, value }) => self.api_key(location, name.as_str(), value.as_str()),
+            Some(AuthProperties::OAuth2 { token_url, client_id, client_secret, scope, refresh_token }) => {
+                let mut provider = OAuth2Provider::builder(token_url.as_str(), client_id.as_str());
+
+                if let Some(client_secret) = client_secret {
+                    provider = provider.client_secret(client_secret.as_str());
+                }
+
+                if let Some(scope) = scope {
+                    provider = provider.scope(scope.as_str());
+                }
+
+                if let Some(refresh_token) = refresh_token {
+                    provider = provider.refresh_token(refresh_token.as_str());
+                }
+
+                self.auth_provider(provider.build())
+            },
+            None => self
+        };
+
+        if let Some(proxy) = properties.proxy {
+            self = self.proxy_properties(proxy);
+        }
+
+        Ok(self)
+    }
+
+    fn proxy_properties(mut self, properties: ProxyProperties) -> ClientixBuilder {
+        let proxies = [
+            properties.url.map(|url| ClientixProxy::all(url.as_str())),
+            properties.http.map(|url| ClientixProxy::http(url.as_str())),
+            properties.https.map(|url| ClientixProxy::https(url.as_str()))
+        ];
+
+        for proxy in proxies.into_iter().flatten() {
+            self = match (&properties.username, &properties.password) {
+                (Some(username), Some(password)) => self.proxy(proxy.basic_auth(username.as_str(), password.as_str())),
+                _ => self.proxy(proxy)
+            };
+        }
+
+        if let Some(no_proxy) = properties.no_proxy {
+            self = self.no_proxy(no_proxy.as_str());
+        }
+
+        if let Some(env) = properties.env {
+            self = self.env_proxy(env);
+        }
+
+        self
+    }
+
     pub fn url(mut self, url: &str) -> ClientixBuilder {
-        self.config.url = Some(url.to_string());
+        self.config.set_url(url);
+        self
+    }
+
+    // replicas of the same service, requests are built against the first one and spread by the balancer
+    pub fn urls(mut self, urls: &[&str]) -> ClientixBuilder {
+        self.config.url = urls.first().map(|url| url.to_string());
+        self.config.urls = urls.iter().map(|url| url.to_string()).collect();
+        self
+    }
+
+    pub fn load_balancing(mut self, strategy: BalanceStrategy) -> ClientixBuilder {
+        self.config.load_balancing = strategy;
+        self
+    }
+
+    pub fn endpoint_ejection(mut self, failures: u32, cool_down: Duration) -> ClientixBuilder {
+        self.config.ejection = EjectionConfig::new(failures, cool_down);
+        self
+    }
 
+    #[cfg(unix)]
+    pub fn unix_socket(mut self, path: impl AsRef<Path>) -> ClientixBuilder {
+        self.config.unix_socket = Some(path.as_ref().to_path_buf());
         self
     }
 
@@ -167,6 +450,102 @@ impl ClientixBuilder {
         self.header(AUTHORIZATION.as_str(), format!("Bearer {}", token).as_str(), true)
     }
 
+    pub fn digest_auth(self, username: &str, password: &str) -> ClientixBuilder {
+        self.auth_provider(DigestAuthProvider::new(username, password))
+    }
+
+    pub fn api_key(mut self, location: ApiKeyLocation, name: &str, value: &str) -> ClientixBuilder {
+        self.config.api_key = Some(ApiKey::new(location, name, value));
+        self
+    }
+
+    pub fn auth_provider(mut self, auth_provider: impl ClientixAuthProvider + 'static) -> ClientixBuilder {
+        self.config.auth_provider = Some(Arc::new(auth_provider));
+        self
+    }
+
+    pub fn signer(mut self, signer: impl ClientixSigner + 'static) -> ClientixBuilder {
+        self.config.signer = Some(Arc::new(signer));
+        self
+    }
+
+    pub fn cookie_store(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.cookie_jar = if enabled { Some(Arc::new(ClientixCookieJar::new())) } else { None };
+        self
+    }
+
+    pub fn cookie_jar(mut self, cookie_jar: Arc<ClientixCookieJar>) -> ClientixBuilder {
+        self.config.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    pub fn tls_root_certificate(mut self, pem: &[u8]) -> ClientixBuilder {
+        self.config.tls.add_root_certificate(pem);
+        self
+    }
+
+    pub fn tls_built_in_roots(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.tls.set_built_in_roots(enabled);
+        self
+    }
+
+    pub fn tls_identity(mut self, certificate_pem: &[u8], key_pem: &[u8]) -> ClientixBuilder {
+        self.config.tls.set_identity(certificate_pem, key_pem);
+        self
+    }
+
+    pub fn tls_pin(mut self, fingerprint: &str) -> ClientixBuilder {
+        self.config.tls.add_pin(fingerprint);
+        self
+    }
+
+    pub fn danger_accept_invalid_certs(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.tls.set_danger_accept_invalid_certs(enabled);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: ClientixProxy) -> ClientixBuilder {
+        self.config.proxies.push(proxy);
+        self
+    }
+
+    pub fn no_proxy(mut self, hosts: &str) -> ClientixBuilder {
+        self.config.no_proxy = Some(hosts.to_string());
+        self
+    }
+
+    pub fn env_proxy(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.env_proxy = enabled;
+        self
+    }
+
+    pub fn redirect(mut self, policy: RedirectPolicy) -> ClientixBuilder {
+        self.config.redirect = policy;
+        self
+    }
+
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> ClientixBuilder {
+        self.config.circuit_breaker = Some(config);
+        self
+    }
+
+    pub fn rate_limit(mut self, config: RateLimitConfig) -> ClientixBuilder {
+        self.config.rate_limit = Some(config);
+        self
+    }
+
+    // a private HTTP cache, e.g. MemoryCache or DiskCache
+    pub fn cache(mut self, storage: impl ClientixCacheStorage + 'static) -> ClientixBuilder {
+        self.config.cache = Some(Arc::new(storage));
+        self
+    }
+
+    // async clients only, concurrent identical GET requests share one call
+    pub fn coalesce(mut self, vary_headers: &[&str]) -> ClientixBuilder {
+        self.config.coalesce = Some(vary_headers.iter().map(|header| header.to_string()).collect());
+        self
+    }
+
     pub fn timeout(mut self, timeout: Duration) -> ClientixBuilder {
         self.config.timeout = Some(timeout);
         self
@@ -187,12 +566,85 @@ impl ClientixBuilder {
         self
     }
 
+    pub fn resolve(mut self, host: &str, addrs: &[SocketAddr]) -> ClientixBuilder {
+        self.config.resolve_overrides.push((host.to_string(), addrs.to_vec()));
+        self
+    }
+
+    pub fn resolver(mut self, resolver: impl ClientixResolver + 'static) -> ClientixBuilder {
+        self.config.resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    pub fn http1_only(mut self) -> ClientixBuilder {
+        self.config.http_version = HttpVersion::Http1Only;
+        self
+    }
+
+    pub fn http2_prior_knowledge(mut self) -> ClientixBuilder {
+        self.config.http_version = HttpVersion::Http2PriorKnowledge;
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> ClientixBuilder {
+        self.config.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> ClientixBuilder {
+        self.config.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn tcp_keepalive(mut self, interval: Duration) -> ClientixBuilder {
+        self.config.tcp_keepalive = Some(interval);
+        self
+    }
+
+    pub fn tcp_nodelay(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.tcp_nodelay = enabled;
+        self
+    }
+
+    pub fn local_address(mut self, address: IpAddr) -> ClientixBuilder {
+        self.config.local_address = Some(address);
+        self
+    }
+
+    pub fn gzip(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.gzip = enabled;
+        self
+    }
+
+    pub fn brotli(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.brotli = enabled;
+        self
+    }
+
+    pub fn deflate(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.deflate = enabled;
+        self
+    }
+
+    pub fn zstd(mut self, enabled: bool) -> ClientixBuilder {
+        self.config.zstd = enabled;
+        self
+    }
+
     pub fn blocking(&self) -> BlockingClient {
-        BlockingClient::from(self.config.clone())
+        self.try_blocking().expect("invalid client configuration")
     }
 
     pub fn asynchronous(&self) -> AsyncClient {
-        AsyncClient::from(self.config.clone())
+        self.try_asynchronous().expect("invalid client configuration")
+    }
+
+    pub fn try_blocking(&self) -> ClientixResult<BlockingClient> {
+        BlockingClient::try_from(self.config.clone())
+    }
+
+    pub fn try_asynchronous(&self) -> ClientixResult<AsyncClient> {
+        AsyncClient::try_from(self.config.clone())
     }
 
     pub fn build(self) -> Clientix {
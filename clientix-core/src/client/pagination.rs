@@ -0,0 +1,220 @@
+use http::header::LINK;
+use http::{HeaderMap, StatusCode};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::client::request::RequestConfig;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PageStrategy {
+    // the next page is the url of the Link header with rel="next"
+    Link,
+    // the next page is requested with the cursor found at a path of the body
+    Cursor { path: String, param: String },
+    // offset/limit query params, the last page is the one shorter than the limit
+    Offset { offset_param: String, limit_param: String, size: usize },
+    // page number (from 1) and page size query params
+    Page { page_param: String, size_param: String, size: usize }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    strategy: PageStrategy,
+    items: Option<String>
+}
+
+// where the next page starts, advanced by every page read
+#[derive(Clone, Debug)]
+enum PagePosition {
+    First,
+    Url(String),
+    // a next link to another origin, requesting it would hand the credentials of the client over
+    Refused(String),
+    Cursor(String),
+    Offset(usize),
+    Page(usize)
+}
+
+pub(crate) struct Pager {
+    pagination: Pagination,
+    next: Option<PagePosition>,
+    origin: Option<String>
+}
+
+impl Pagination {
+
+    pub fn new(strategy: PageStrategy) -> Self {
+        Pagination { strategy, items: None }
+    }
+
+    pub fn link() -> Self {
+        Pagination::new(PageStrategy::Link)
+    }
+
+    pub fn cursor(path: &str, param: &str) -> Self {
+        Pagination::new(PageStrategy::Cursor { path: path.to_string(), param: param.to_string() })
+    }
+
+    pub fn offset(offset_param: &str, limit_param: &str, size: usize) -> Self {
+        Pagination::new(PageStrategy::Offset { offset_param: offset_param.to_string(), limit_param: limit_param.to_string(), size: size.max(1) })
+    }
+
+    pub fn page(page_param: &str, size_param: &str, size: usize) -> Self {
+        Pagination::new(PageStrategy::Page { page_param: page_param.to_string(), size_param: size_param.to_string(), size: size.max(1) })
+    }
+
+    // dot separated path of the items array in the body, e.g. "data" or "result.items"; the body itself by default
+    pub fn items(mut self, path: &str) -> Self {
+        self.items = Some(path.to_string());
+        self
+    }
+
+    pub fn strategy(&self) -> &PageStrategy {
+        &self.strategy
+    }
+
+}
+
+impl Pager {
+
+    pub(crate) fn new(pagination: Pagination) -> Self {
+        Pager { pagination, next: Some(PagePosition::First), origin: None }
+    }
+
+    // the request of the next page built from the one of the generated method, None after the last page
+    pub(crate) fn next_config(&self, template: &RequestConfig) -> Option<RequestConfig> {
+        let mut config = template.clone();
+        match (self.next.as_ref()?, &self.pagination.strategy) {
+            (PagePosition::Url(url), _) => {
+                config.set_url(url.as_str());
+                config.set_queries(Default::default());
+            },
+            (PagePosition::Cursor(cursor), PageStrategy::Cursor { param, .. }) => config.set_query(param, cursor),
+            (PagePosition::First, PageStrategy::Offset { offset_param, limit_param, size }) => {
+                config.set_query(offset_param, "0");
+                config.set_query(limit_param, size.to_string().as_str());
+            },
+            (PagePosition::Offset(offset), PageStrategy::Offset { offset_param, limit_param, size }) => {
+                config.set_query(offset_param, offset.to_string().as_str());
+                config.set_query(limit_param, size.to_string().as_str());
+            },
+            (PagePosition::First, PageStrategy::Page { page_param, size_param, size }) => {
+                config.set_query(page_param, "1");
+                config.set_query(size_param, size.to_string().as_str());
+            },
+            (PagePosition::Page(page), PageStrategy::Page { page_param, size_param, size }) => {
+                config.set_query(page_param, page.to_string().as_str());
+                config.set_query(size_param, size.to_string().as_str());
+            },
+            _ => ()
+        }
+
+        Some(config)
+    }
+
+    // the items of a page; an error or an empty page ends the pagination
+    pub(crate) fn read<T>(&mut self, url: &Url, status: StatusCode, headers: &HeaderMap, body: &[u8]) -> ClientixResult<Vec<T>> where T: DeserializeOwned {
+        let current = self.next.take();
+        let origin = self.origin.get_or_insert_with(|| url.origin().ascii_serialization());
+        if !status.is_success() {
+            return Err(pagination_error(format!("unexpected status {status} for page {url}")));
+        }
+
+        let body = serde_json::from_slice::<Value>(body)?;
+        let items = match &self.pagination.items {
+            Some(path) => lookup(&body, path),
+            None => Some(&body)
+        };
+
+        let items = match items {
+            Some(Value::Array(items)) => items.iter()
+                .map(|item| serde_json::from_value::<T>(item.clone()))
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(pagination_error(format!("no items array in page {url}")))
+        };
+
+        if items.is_empty() {
+            return Ok(items);
+        }
+
+        self.next = match &self.pagination.strategy {
+            PageStrategy::Link => next_link(url, headers).map(|next| match next.origin().ascii_serialization() == *origin {
+                true => PagePosition::Url(next.to_string()),
+                false => PagePosition::Refused(next.to_string())
+            }),
+            PageStrategy::Cursor { path, .. } => {
+                let cursor = match lookup(&body, path) {
+                    Some(Value::String(cursor)) if !cursor.is_empty() => Some(cursor.clone()),
+                    Some(Value::Number(cursor)) => Some(cursor.to_string()),
+                    _ => None
+                };
+
+                // a cursor pointing at the page just read would loop forever
+                match (cursor, current) {
+                    (Some(cursor), Some(PagePosition::Cursor(previous))) if cursor == previous => None,
+                    (cursor, _) => cursor.map(PagePosition::Cursor)
+                }
+            },
+            PageStrategy::Offset { size, .. } if items.len() >= *size => match current {
+                Some(PagePosition::Offset(offset)) => Some(PagePosition::Offset(offset + items.len())),
+                _ => Some(PagePosition::Offset(items.len()))
+            },
+            PageStrategy::Page { size, .. } if items.len() >= *size => match current {
+                Some(PagePosition::Page(page)) => Some(PagePosition::Page(page + 1)),
+                _ => Some(PagePosition::Page(2))
+            },
+            _ => None
+        };
+
+        Ok(items)
+    }
+
+    // fails instead of requesting a next page on another origin than the first one
+    pub(crate) fn check_next(&self) -> ClientixResult<()> {
+        match &self.next {
+            Some(PagePosition::Refused(url)) => Err(pagination_error(format!("cross-origin next page refused: {url}"))),
+            _ => Ok(())
+        }
+    }
+
+    pub(crate) fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.next = None;
+    }
+
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            Value::Array(values) => values.get(segment.parse::<usize>().ok()?),
+            value => value.get(segment)
+        })
+}
+
+// Link: <https://api.example.com/items?page=2>; rel="next", <...>; rel="last"
+fn next_link(url: &Url, headers: &HeaderMap) -> Option<Url> {
+    headers.get_all(LINK).iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (target, params) = link.trim().split_once(';')?;
+            let next = params.split(';')
+                .filter_map(|param| param.trim().split_once('='))
+                .any(|(name, value)| name.trim().eq_ignore_ascii_case("rel") && value.trim().trim_matches('"').split_whitespace().any(|rel| rel.eq_ignore_ascii_case("next")));
+
+            match next {
+                true => url.join(target.trim().strip_prefix('<')?.strip_suffix('>')?).ok(),
+                false => None
+            }
+        })
+}
+
+fn pagination_error(message: String) -> ClientixError {
+    ClientixError::InvalidResponse(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
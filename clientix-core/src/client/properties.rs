@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde::Deserialize;
+use serde_json::Value;
+use crate::client::auth::api_key::ApiKeyLocation;
+use crate::client::balancer::BalanceStrategy;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::env::interpolate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ClientixProperties {
+    pub url: Option<String>,
+    pub urls: Vec<String>,
+    pub load_balancing: Option<BalanceStrategy>,
+    pub path: Option<String>,
+    pub user_agent: Option<String>,
+    pub headers: HashMap<String, String>,
+    pub timeout: Option<String>,
+    pub read_timeout: Option<String>,
+    pub connect_timeout: Option<String>,
+    pub connection_verbose: Option<bool>,
+    pub auth: Option<AuthProperties>,
+    pub proxy: Option<ProxyProperties>
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ProxyProperties {
+    pub url: Option<String>,
+    pub http: Option<String>,
+    pub https: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub no_proxy: Option<String>,
+    pub env: Option<bool>
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AuthProperties {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+    Digest { username: String, password: String },
+    #[serde(rename = "api_key")]
+    ApiKey {
+        #[serde(rename = "in", default)]
+        location: ApiKeyLocation,
+        name: String,
+        value: String
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        token_url: String,
+        client_id: String,
+        client_secret: Option<String>,
+        scope: Option<String>,
+        refresh_token: Option<String>
+    }
+}
+
+impl ConfigFormat {
+
+    pub fn from_path(path: &Path) -> ClientixResult<ConfigFormat> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
+            _ => Err(config_error(format!("unsupported config format: {}", path.display()), None))
+        }
+    }
+
+}
+
+impl ClientixProperties {
+
+    pub fn load(path: impl AsRef<Path>, name: &str) -> ClientixResult<ClientixProperties> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        let content = fs::read_to_string(path)
+            .map_err(|err| ClientixError::IO(ClientixErrorData::new(), Some(err.into())))?;
+
+        ClientixProperties::parse(content.as_str(), format, name)
+    }
+
+    pub fn parse(content: &str, format: ConfigFormat, name: &str) -> ClientixResult<ClientixProperties> {
+        let document: Value = match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|err| config_error("invalid toml config".to_string(), Some(err.into())))?,
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|err| config_error("invalid json config".to_string(), Some(err.into())))?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|err| config_error("invalid yaml config".to_string(), Some(err.into())))?,
+        };
+
+        let section = name.split('.')
+            .try_fold(&document, |value, key| value.get(key))
+            .ok_or_else(|| config_error(format!("missing client config section: {name}"), None))?;
+
+        let properties = serde_json::from_value::<ClientixProperties>(section.clone())
+            .map_err(|err| config_error(format!("invalid client config section: {name}"), Some(err.into())))?;
+
+        properties.interpolate()
+    }
+
+    fn interpolate(mut self) -> ClientixResult<ClientixProperties> {
+        self.url = self.url.as_deref().map(interpolate).transpose()?;
+        self.urls = self.urls.iter().map(|url| interpolate(url.as_str())).collect::<ClientixResult<Vec<_>>>()?;
+        self.path = self.path.as_deref().map(interpolate).transpose()?;
+        self.user_agent = self.user_agent.as_deref().map(interpolate).transpose()?;
+        self.timeout = self.timeout.as_deref().map(interpolate).transpose()?;
+        self.read_timeout = self.read_timeout.as_deref().map(interpolate).transpose()?;
+        self.connect_timeout = self.connect_timeout.as_deref().map(interpolate).transpose()?;
+
+        for value in self.headers.values_mut() {
+            *value = interpolate(value.as_str())?;
+        }
+
+        self.auth = match self.auth {
+            Some(AuthProperties::Basic { username, password }) => Some(AuthProperties::Basic {
+                username: interpolate(username.as_str())?,
+                password: interpolate(password.as_str())?
+            }),
+            Some(AuthProperties::Bearer { token }) => Some(AuthProperties::Bearer {
+                token: interpolate(token.as_str())?
+            }),
+            Some(AuthProperties::Digest { username, password }) => Some(AuthProperties::Digest {
+                username: interpolate(username.as_str())?,
+                password: interpolate(password.as_str())?
+            }),
+            Some(AuthProperties::ApiKey { location, name, value }) => Some(AuthProperties::ApiKey {
+                location,
+                name: interpolate(name.as_str())?,
+                value: interpolate(value.as_str())?
+            }),
+            Some(AuthProperties::OAuth2 { token_url, client_id, client_secret, scope, refresh_token }) => Some(AuthProperties::OAuth2 {
+                token_url: interpolate(token_url.as_str())?,
+                client_id: interpolate(client_id.as_str())?,
+                client_secret: client_secret.as_deref().map(interpolate).transpose()?,
+                scope: scope.as_deref().map(interpolate).transpose()?,
+                refresh_token: refresh_token.as_deref().map(interpolate).transpose()?
+            }),
+            None => None
+        };
+
+        if let Some(proxy) = self.proxy.as_mut() {
+            proxy.url = proxy.url.as_deref().map(interpolate).transpose()?;
+            proxy.http = proxy.http.as_deref().map(interpolate).transpose()?;
+            proxy.https = proxy.https.as_deref().map(interpolate).transpose()?;
+            proxy.username = proxy.username.as_deref().map(interpolate).transpose()?;
+            proxy.password = proxy.password.as_deref().map(interpolate).transpose()?;
+            proxy.no_proxy = proxy.no_proxy.as_deref().map(interpolate).transpose()?;
+        }
+
+        Ok(self)
+    }
+
+}
+
+fn config_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,82 @@
+use std::fmt::{Debug, Formatter};
+use reqwest::{NoProxy, Proxy};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProxyScheme {
+    Http,
+    Https,
+    All
+}
+
+#[derive(Clone)]
+pub struct ClientixProxy {
+    scheme: ProxyScheme,
+    url: String,
+    credentials: Option<(String, String)>
+}
+
+impl ClientixProxy {
+
+    pub fn new(scheme: ProxyScheme, url: &str) -> Self {
+        ClientixProxy { scheme, url: url.to_string(), credentials: None }
+    }
+
+    pub fn http(url: &str) -> Self {
+        ClientixProxy::new(ProxyScheme::Http, url)
+    }
+
+    pub fn https(url: &str) -> Self {
+        ClientixProxy::new(ProxyScheme::Https, url)
+    }
+
+    // proxies every request, socks5:// and socks5h:// urls are supported as well
+    pub fn all(url: &str) -> Self {
+        ClientixProxy::new(ProxyScheme::All, url)
+    }
+
+    pub fn basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    pub fn scheme(&self) -> ProxyScheme {
+        self.scheme
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn build(&self, no_proxy: Option<&str>) -> ClientixResult<Proxy> {
+        let proxy = match self.scheme {
+            ProxyScheme::Http => Proxy::http(self.url.as_str()),
+            ProxyScheme::Https => Proxy::https(self.url.as_str()),
+            ProxyScheme::All => Proxy::all(self.url.as_str())
+        }.map_err(|err| proxy_error(format!("invalid proxy url: {}", self.url), Some(err.into())))?;
+
+        let proxy = match &self.credentials {
+            Some((username, password)) => proxy.basic_auth(username, password),
+            None => proxy
+        };
+
+        Ok(proxy.no_proxy(no_proxy.and_then(NoProxy::from_string)))
+    }
+
+}
+
+impl Debug for ClientixProxy {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientixProxy")
+            .field("scheme", &self.scheme)
+            .field("url", &self.url)
+            .field("username", &self.credentials.as_ref().map(|(username, _)| username))
+            .finish()
+    }
+
+}
+
+fn proxy_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use http::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION, PROXY_AUTHORIZATION, TRANSFER_ENCODING, WWW_AUTHENTICATE};
+use http::{Extensions, HeaderMap, Method, StatusCode};
+use reqwest::Url;
+use crate::client::auth::api_key::ApiKey;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+type RedirectCallback = dyn Fn(&RedirectAttempt) -> RedirectAction + Send + Sync;
+
+#[derive(Clone)]
+pub enum RedirectPolicy {
+    None,
+    Limited(usize),
+    SameOrigin(usize),
+    Custom(Arc<RedirectCallback>)
+}
+
+pub struct RedirectAttempt<'a> {
+    status: StatusCode,
+    url: &'a Url,
+    previous: &'a [Url]
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RedirectAction {
+    Follow,
+    Stop,
+    Error(String)
+}
+
+// the urls that answered with a redirect before the final response, kept in the response extensions
+#[derive(Clone, Debug, Default)]
+pub struct RedirectHistory(pub Vec<Url>);
+
+pub struct RedirectTarget {
+    method: Method,
+    url: Url,
+    keep_body: bool,
+    cross_origin: bool
+}
+
+impl Default for RedirectPolicy {
+
+    fn default() -> Self {
+        RedirectPolicy::Limited(DEFAULT_MAX_REDIRECTS)
+    }
+
+}
+
+impl RedirectPolicy {
+
+    pub fn none() -> Self {
+        RedirectPolicy::None
+    }
+
+    pub fn limited(max: usize) -> Self {
+        RedirectPolicy::Limited(max)
+    }
+
+    pub fn same_origin() -> Self {
+        RedirectPolicy::SameOrigin(DEFAULT_MAX_REDIRECTS)
+    }
+
+    pub fn custom<F>(callback: F) -> Self where F: Fn(&RedirectAttempt) -> RedirectAction + Send + Sync + 'static {
+        RedirectPolicy::Custom(Arc::new(callback))
+    }
+
+    // previous holds every url requested so far, the last one is the url that answered with the redirect
+    pub fn resolve(&self, method: &Method, status: StatusCode, headers: &HeaderMap, previous: &[Url]) -> ClientixResult<Option<RedirectTarget>> {
+        let current = match previous.last() {
+            Some(current) => current,
+            None => return Ok(None)
+        };
+
+        let (method, keep_body) = match status {
+            StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND if *method == Method::POST => (Method::GET, false),
+            StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => (method.clone(), true),
+            StatusCode::SEE_OTHER if *method == Method::HEAD => (Method::HEAD, false),
+            StatusCode::SEE_OTHER => (Method::GET, false),
+            StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT => (method.clone(), true),
+            _ => return Ok(None)
+        };
+
+        // a redirect without a usable location is handed to the caller as is
+        let url = match headers.get(LOCATION).and_then(|location| location.to_str().ok()).and_then(|location| current.join(location).ok()) {
+            Some(url) => url,
+            None => return Ok(None)
+        };
+
+        let cross_origin = url.origin() != current.origin();
+        let attempt = RedirectAttempt { status, url: &url, previous };
+
+        let action = match self {
+            RedirectPolicy::None => RedirectAction::Stop,
+            RedirectPolicy::Limited(max) => attempt.limit(*max),
+            RedirectPolicy::SameOrigin(_) if cross_origin => RedirectAction::Error(format!("cross-origin redirect refused: {url}")),
+            RedirectPolicy::SameOrigin(max) => attempt.limit(*max),
+            RedirectPolicy::Custom(callback) => callback(&attempt)
+        };
+
+        match action {
+            RedirectAction::Follow => Ok(Some(RedirectTarget { method, url, keep_body, cross_origin })),
+            RedirectAction::Stop => Ok(None),
+            RedirectAction::Error(message) => Err(redirect_error(message))
+        }
+    }
+
+}
+
+impl RedirectAttempt<'_> {
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn url(&self) -> &Url {
+        self.url
+    }
+
+    pub fn previous(&self) -> &[Url] {
+        self.previous
+    }
+
+    fn limit(&self, max: usize) -> RedirectAction {
+        match self.previous.len() > max {
+            true => RedirectAction::Error(format!("too many redirects, the limit is {max}")),
+            false => RedirectAction::Follow
+        }
+    }
+
+}
+
+impl RedirectHistory {
+
+    pub fn from_extensions(extensions: &Extensions) -> Vec<Url> {
+        extensions.get::<RedirectHistory>().map(|history| history.0.clone()).unwrap_or_default()
+    }
+
+}
+
+impl RedirectTarget {
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn keep_body(&self) -> bool {
+        self.keep_body
+    }
+
+    pub fn prepare(&self, url: &mut Url, headers: &mut HeaderMap, api_key: Option<&ApiKey>) {
+        if !self.keep_body {
+            for header in [CONTENT_TYPE, CONTENT_LENGTH, CONTENT_ENCODING, TRANSFER_ENCODING] {
+                headers.remove(header);
+            }
+        }
+
+        // credentials never follow a redirect to another origin: auth headers, the api key and every sensitive header
+        if self.cross_origin {
+            for header in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE] {
+                headers.remove(header);
+            }
+
+            let sensitive = headers.iter()
+                .filter(|(_, value)| value.is_sensitive())
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+
+            for header in sensitive {
+                headers.remove(header);
+            }
+
+            if let Some(api_key) = api_key {
+                api_key.strip(url, headers);
+            }
+        }
+    }
+
+}
+
+fn redirect_error(message: String) -> ClientixError {
+    ClientixError::Http(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
@@ -3,18 +3,32 @@ use std::time::Duration;
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use http::{HeaderMap, HeaderName, HeaderValue};
-use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use http::header::{AUTHORIZATION, CONTENT_TYPE, COOKIE};
 use serde::Serialize;
+use crate::client::cookie::encode_cookie_value;
+use crate::client::hedge::HedgePolicy;
+use crate::client::limiter::RateLimitConfig;
 use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+use crate::core::headers::content_encoding::ContentEncoding;
 use crate::core::headers::content_type::ContentType;
+use crate::core::headers::idempotency_key;
+use crate::core::headers::idempotency_key::IDEMPOTENCY_KEY;
+use crate::core::query::style::QueryStyle;
 
 #[derive(Clone, Debug, Default)]
 pub struct RequestConfig {
+    url: Option<String>,
     path: String,
     headers: HeaderMap,
     queries: Vec<(String, String)>,
     body: Option<String>,
-    timeout: Option<Duration>
+    timeout: Option<Duration>,
+    compression: Option<ContentEncoding>,
+    operation: Option<String>,
+    rate_limit: Option<RateLimitConfig>,
+    hedge: Option<HedgePolicy>,
+    streaming: bool,
+    digest_auth: Option<(String, String)>
 }
 
 pub trait ClientixRequestBuilder {
@@ -41,11 +55,37 @@ pub trait ClientixRequestBuilder {
         self
     }
 
+    fn query_list(mut self, key: &str, values: Vec<String>, style: QueryStyle) -> Self where Self: Sized {
+        self.config().add_query_list(key, values, style);
+        self
+    }
+
+    fn query_object<T: Serialize>(mut self, object: T) -> Self where Self: Sized {
+        if let Err(error) = self.config().add_query_object(object) {
+            *self.result() = Err(error);
+        }
+
+        self
+    }
+
     fn header(mut self, key: &str, value: &str) -> Self where Self: Sized {
         self.config().set_header(key, value, false);
         self
     }
 
+    fn sensitive_header(mut self, key: &str, value: &str) -> Self where Self: Sized {
+        self.config().set_header(key, value, true);
+        self
+    }
+
+    fn optional_header(mut self, key: &str, value: Option<String>, sensitive: bool) -> Self where Self: Sized {
+        if let Some(value) = value {
+            self.config().set_header(key, value.as_str(), sensitive);
+        }
+
+        self
+    }
+
     fn headers(mut self, headers: HashMap<String, String>) -> Self where Self: Sized {
         for (key, value) in headers {
             self = self.header(key.as_str(), value.as_str());
@@ -54,6 +94,24 @@ pub trait ClientixRequestBuilder {
         self
     }
 
+    fn cookie(mut self, name: &str, value: &str) -> Self where Self: Sized {
+        self.config().add_cookie(name, value);
+        self
+    }
+
+    fn optional_cookie(mut self, name: &str, value: Option<String>) -> Self where Self: Sized {
+        if let Some(value) = value {
+            self.config().add_cookie(name, value.as_str());
+        }
+
+        self
+    }
+
+    fn header_map(mut self, headers: &HeaderMap) -> Self where Self: Sized {
+        self.config().add_header_map(headers);
+        self
+    }
+
     fn basic_auth(mut self, username: &str, password: &str) -> Self where Self: Sized {
         self.config().basic_auth(username, password);
         self
@@ -64,10 +122,50 @@ pub trait ClientixRequestBuilder {
         self
     }
 
+    fn digest_auth(mut self, username: &str, password: &str) -> Self where Self: Sized {
+        self.config().digest_auth(username, password);
+        self
+    }
+
     fn body<T: Serialize>(mut self, body: T, content_type: ContentType) -> Self where Self: Sized {
         *self.result() = self.config().set_body(body, content_type);
         self
     }
+
+    fn compress(mut self, encoding: ContentEncoding) -> Self where Self: Sized {
+        self.config().set_compression(encoding);
+        self
+    }
+
+    // names the request after the generated method, per-method circuits and rate limits are keyed by it
+    fn operation(mut self, name: &str) -> Self where Self: Sized {
+        self.config().set_operation(name);
+        self
+    }
+
+    fn rate_limit(mut self, config: RateLimitConfig) -> Self where Self: Sized {
+        self.config().set_rate_limit(config);
+        self
+    }
+
+    // only async requests are hedged, and only safe ones such as GET and HEAD
+    fn hedge(mut self, policy: HedgePolicy) -> Self where Self: Sized {
+        self.config().set_hedge(policy);
+        self
+    }
+
+    // the same key is sent with every retry of the call, a new one is generated when none is given
+    fn idempotency_key(mut self, key: Option<&str>) -> Self where Self: Sized {
+        let key = key.map(str::to_string).unwrap_or_else(idempotency_key::generate);
+        self.config().set_header(IDEMPOTENCY_KEY, key.as_str(), false);
+        self
+    }
+
+    // the response body is read as a stream, such a request never shares its response with others
+    fn streaming(mut self) -> Self where Self: Sized {
+        self.config().set_streaming(true);
+        self
+    }
     
 }
 
@@ -75,14 +173,30 @@ impl RequestConfig {
     
     pub fn new() -> Self {
         RequestConfig {
+            url: None,
             path: Default::default(),
             headers: Default::default(),
             queries: Default::default(),
             body: None,
             timeout: None,
+            compression: None,
+            operation: None,
+            rate_limit: None,
+            hedge: None,
+            streaming: false,
+            digest_auth: None,
         }
     }
     
+    // an absolute url replacing the client url and path, e.g. the next page of a Link header
+    pub fn get_url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn set_url(&mut self, url: &str) {
+        self.url = Some(url.to_string());
+    }
+
     pub fn get_path(&self) -> &String { 
         &self.path 
     }
@@ -99,6 +213,27 @@ impl RequestConfig {
         self.queries.push((key.to_string(), value.to_string()));
     }
 
+    pub fn set_query(&mut self, key: &str, value: &str) {
+        self.queries.retain(|(name, _)| name != key);
+        self.queries.push((key.to_string(), value.to_string()));
+    }
+
+    pub fn add_query_list(&mut self, key: &str, values: Vec<String>, style: QueryStyle) {
+        self.queries.extend(style.format(key, values));
+    }
+
+    pub fn add_query_object<T: Serialize>(&mut self, object: T) -> ClientixResult<()> {
+        let encoded = serde_urlencoded::to_string(&object)
+            .map_err(|err| ClientixError::InvalidRequest(Default::default(), Some(err.into())))?;
+
+        let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(encoded.as_str())
+            .map_err(|err| ClientixError::InvalidRequest(Default::default(), Some(err.into())))?;
+
+        self.queries.extend(pairs);
+
+        Ok(())
+    }
+
     pub fn add_queries(&mut self, queries: HashMap<String, String>) {
         for (key, value) in queries {
             self.queries.push((key, value));
@@ -141,6 +276,22 @@ impl RequestConfig {
         }
     }
 
+    pub fn add_header_map(&mut self, headers: &HeaderMap) {
+        for (key, value) in headers.iter() {
+            self.headers.append(key.clone(), value.clone());
+        }
+    }
+
+    pub fn add_cookie(&mut self, name: &str, value: &str) {
+        let value = encode_cookie_value(value);
+        let cookie = match self.headers.get(COOKIE).and_then(|value| value.to_str().ok()) {
+            Some(cookies) => format!("{cookies}; {name}={value}"),
+            None => format!("{name}={value}")
+        };
+
+        self.set_header(COOKIE.as_str(), cookie.as_str(), true);
+    }
+
     pub fn basic_auth(&mut self, username: &str, password: &str) {
         let basic_token = format!("Basic {}", BASE64_STANDARD.encode(format!("{username}:{password}")));
         self.set_header(AUTHORIZATION.as_str(), basic_token.as_str(), true);
@@ -150,6 +301,15 @@ impl RequestConfig {
         self.set_header(AUTHORIZATION.as_str(), format!("Bearer {}", token).as_str(), true);
     }
 
+    // the provider itself belongs to the client, requests with the same credentials share its digest session
+    pub fn digest_auth(&mut self, username: &str, password: &str) {
+        self.digest_auth = Some((username.to_string(), password.to_string()));
+    }
+
+    pub fn get_digest_auth(&self) -> Option<(&str, &str)> {
+        self.digest_auth.as_ref().map(|(username, password)| (username.as_str(), password.as_str()))
+    }
+
     pub fn get_body(&self) -> &Option<String> {
         &self.body
     }
@@ -208,5 +368,45 @@ impl RequestConfig {
     pub fn set_timeout(&mut self, timeout: Duration) {
         self.timeout = Some(timeout);
     }
+
+    pub fn get_compression(&self) -> Option<ContentEncoding> {
+        self.compression
+    }
+
+    pub fn set_compression(&mut self, encoding: ContentEncoding) {
+        self.compression = Some(encoding);
+    }
+
+    pub fn get_operation(&self) -> Option<&str> {
+        self.operation.as_deref()
+    }
+
+    pub fn set_operation(&mut self, name: &str) {
+        self.operation = Some(name.to_string());
+    }
+
+    pub fn get_rate_limit(&self) -> Option<&RateLimitConfig> {
+        self.rate_limit.as_ref()
+    }
+
+    pub fn set_rate_limit(&mut self, config: RateLimitConfig) {
+        self.rate_limit = Some(config);
+    }
+
+    pub fn get_hedge(&self) -> Option<HedgePolicy> {
+        self.hedge
+    }
+
+    pub fn set_hedge(&mut self, policy: HedgePolicy) {
+        self.hedge = Some(policy);
+    }
+
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    pub fn set_streaming(&mut self, streaming: bool) {
+        self.streaming = streaming;
+    }
     
 }
\ No newline at end of file
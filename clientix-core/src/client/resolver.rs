@@ -0,0 +1,45 @@
+use std::fmt::{Debug, Formatter};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use futures_util::future::BoxFuture;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use crate::client::response::ClientixResult;
+
+// a resolver may return port 0, the port of the request url or the scheme default is used then
+pub trait ClientixResolver: Send + Sync {
+
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, ClientixResult<Vec<SocketAddr>>>;
+
+}
+
+impl Debug for dyn ClientixResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientixResolver")
+    }
+}
+
+pub struct ResolverAdapter {
+    resolver: Arc<dyn ClientixResolver>
+}
+
+impl ResolverAdapter {
+
+    pub fn new(resolver: Arc<dyn ClientixResolver>) -> Self {
+        ResolverAdapter { resolver }
+    }
+
+}
+
+impl Resolve for ResolverAdapter {
+
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.resolver.clone();
+        let host = name.as_str().to_string();
+
+        Box::pin(async move {
+            let addrs = resolver.resolve(host.as_str()).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+
+}
@@ -2,6 +2,7 @@ use std::net::SocketAddr;
 use reqwest::{StatusCode, Url, Version};
 use reqwest::header::HeaderMap;
 use thiserror::Error;
+use crate::client::cache::CacheStatus;
 
 pub type ClientixResult<T> = Result<T, ClientixError>;
 
@@ -25,9 +26,21 @@ pub enum ClientixError {
     #[error("Invalid request")]
     InvalidRequest(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
 
+    #[error("Authentication error")]
+    Auth(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
+
+    #[error("Configuration error")]
+    Config(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
+
     #[error("invalid response")]
     InvalidResponse(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
 
+    #[error("Circuit open")]
+    CircuitOpen(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
+
+    #[error("Rate limited")]
+    RateLimited(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
+
     #[error("Other error")]
     Other(ClientixErrorData, #[source] Option<Box<dyn std::error::Error + Send + Sync>>),
 }
@@ -40,6 +53,8 @@ pub struct ClientixResponse<T> {
     url: Url,
     remote_addr: Option<SocketAddr>,
     headers: HeaderMap,
+    redirects: Vec<Url>,
+    cache_status: Option<CacheStatus>,
     body: T
 }
 
@@ -122,10 +137,22 @@ impl <T> ClientixResponse<T> where T: Clone {
            url,
            remote_addr,
            headers,
+           redirects: vec![],
+           cache_status: None,
            body
        }
     }
 
+    pub fn with_redirects(mut self, redirects: Vec<Url>) -> ClientixResponse<T> {
+        self.redirects = redirects;
+        self
+    }
+
+    pub fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> ClientixResponse<T> {
+        self.cache_status = cache_status;
+        self
+    }
+
     pub fn version(&self) -> Version {
         self.version
     }
@@ -150,6 +177,15 @@ impl <T> ClientixResponse<T> where T: Clone {
         &self.headers
     }
 
+    pub fn redirects(&self) -> &[Url] {
+        &self.redirects
+    }
+
+    // None when the client has no cache or the request bypassed it
+    pub fn cache_status(&self) -> Option<CacheStatus> {
+        self.cache_status
+    }
+
     pub fn body(&self) -> T {
         self.body.clone()
     }
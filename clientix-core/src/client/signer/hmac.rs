@@ -0,0 +1,75 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use ::hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::client::response::ClientixResult;
+use crate::client::signer::{hex_encode, ClientixSigner, SignableRequest};
+
+const DEFAULT_SIGNATURE_HEADER: &str = "x-signature";
+const DEFAULT_SIGNED_HEADER: &str = "host";
+
+pub struct HmacSigner {
+    secret: Vec<u8>,
+    signature_header: String,
+    timestamp_header: Option<String>,
+    signed_headers: Vec<String>
+}
+
+impl HmacSigner {
+
+    pub fn new(secret: &[u8]) -> Self {
+        HmacSigner {
+            secret: secret.to_vec(),
+            signature_header: DEFAULT_SIGNATURE_HEADER.to_string(),
+            timestamp_header: None,
+            signed_headers: vec![DEFAULT_SIGNED_HEADER.to_string()]
+        }
+    }
+
+    pub fn signature_header(mut self, name: &str) -> Self {
+        self.signature_header = name.to_ascii_lowercase();
+        self
+    }
+
+    pub fn timestamp_header(mut self, name: &str) -> Self {
+        self.timestamp_header = Some(name.to_ascii_lowercase());
+        self
+    }
+
+    pub fn signed_headers(mut self, names: &[&str]) -> Self {
+        self.signed_headers = names.iter().map(|name| name.to_ascii_lowercase()).collect();
+        self
+    }
+
+    pub fn sign_at(&self, request: &mut SignableRequest, time: SystemTime) -> ClientixResult<()> {
+        let mut signed_headers = self.signed_headers.clone();
+
+        if let Some(timestamp_header) = &self.timestamp_header {
+            let timestamp = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            request.set_header(timestamp_header.as_str(), timestamp.to_string().as_str(), false)?;
+            signed_headers.push(timestamp_header.clone());
+        }
+
+        signed_headers.sort();
+        signed_headers.dedup();
+
+        let canonical_request = request.canonical_request(request.url().path(), &signed_headers);
+        let signature = hex_encode(&hmac_sha256(&self.secret, canonical_request.as_bytes()));
+
+        request.set_header(self.signature_header.as_str(), signature.as_str(), true)
+    }
+
+}
+
+impl ClientixSigner for HmacSigner {
+
+    fn sign(&self, request: &mut SignableRequest) -> ClientixResult<()> {
+        self.sign_at(request, SystemTime::now())
+    }
+
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
@@ -0,0 +1,148 @@
+pub mod hmac;
+pub mod sigv4;
+
+use std::fmt::{Debug, Formatter};
+use http::{HeaderMap, HeaderName, HeaderValue, Method};
+use http::header::HOST;
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+pub trait ClientixSigner: Send + Sync {
+
+    fn sign(&self, request: &mut SignableRequest) -> ClientixResult<()>;
+
+}
+
+impl Debug for dyn ClientixSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientixSigner")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SignableRequest {
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+    body: Vec<u8>
+}
+
+impl SignableRequest {
+
+    pub fn new(method: Method, url: Url, headers: HeaderMap, body: Vec<u8>) -> Self {
+        SignableRequest { method, url, headers, body }
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn url_mut(&mut self) -> &mut Url {
+        &mut self.url
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str, sensitive: bool) -> ClientixResult<()> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|err| signer_error(format!("invalid header name: {name}"), Some(err.into())))?;
+
+        let mut value = HeaderValue::from_str(value)
+            .map_err(|err| signer_error(format!("invalid header value: {name}"), Some(err.into())))?;
+        value.set_sensitive(sensitive);
+
+        self.headers.insert(name, value);
+
+        Ok(())
+    }
+
+    pub fn host(&self) -> String {
+        let host = self.url.host_str().unwrap_or_default();
+
+        match self.url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string()
+        }
+    }
+
+    pub fn payload_hash(&self) -> String {
+        hex_encode(Sha256::digest(&self.body).as_slice())
+    }
+
+    // method, canonical path, sorted query, selected headers and the body hash, one per line
+    pub fn canonical_request(&self, canonical_path: &str, signed_headers: &[String]) -> String {
+        let mut queries = self.url.query_pairs()
+            .map(|(key, value)| (uri_encode(key.as_ref(), true), uri_encode(value.as_ref(), true)))
+            .collect::<Vec<_>>();
+        queries.sort();
+
+        let canonical_query = queries.iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_headers = signed_headers.iter()
+            .map(|name| format!("{name}:{}\n", self.canonical_header_value(name)))
+            .collect::<String>();
+
+        let payload_hash = match self.headers.get("x-amz-content-sha256").and_then(|value| value.to_str().ok()) {
+            Some(payload_hash) => payload_hash.to_string(),
+            None => self.payload_hash()
+        };
+
+        format!(
+            "{}\n{canonical_path}\n{canonical_query}\n{canonical_headers}\n{}\n{payload_hash}",
+            self.method.as_str(),
+            signed_headers.join(";")
+        )
+    }
+
+    fn canonical_header_value(&self, name: &str) -> String {
+        if name == HOST.as_str() && !self.headers.contains_key(HOST) {
+            return self.host();
+        }
+
+        self.headers.get_all(name).iter()
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+}
+
+pub fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(format!("%{byte:02X}").as_str())
+        }
+    }
+
+    encoded
+}
+
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn signer_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Auth(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,130 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use http::header::{AUTHORIZATION, CONTENT_TYPE, HOST, RANGE};
+use sha2::{Digest, Sha256};
+use crate::client::response::ClientixResult;
+use crate::client::signer::hmac::hmac_sha256;
+use crate::client::signer::{hex_encode, uri_encode, ClientixSigner, SignableRequest};
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const TERMINATOR: &str = "aws4_request";
+const S3_SERVICE: &str = "s3";
+const AMZ_DATE_HEADER: &str = "x-amz-date";
+const AMZ_CONTENT_SHA256_HEADER: &str = "x-amz-content-sha256";
+const AMZ_SECURITY_TOKEN_HEADER: &str = "x-amz-security-token";
+const AMZ_HEADER_PREFIX: &str = "x-amz-";
+const CONTENT_MD5_HEADER: &str = "content-md5";
+
+pub struct SigV4Signer {
+    access_key: String,
+    secret_key: String,
+    session_token: Option<String>,
+    region: String,
+    service: String
+}
+
+impl SigV4Signer {
+
+    pub fn new(access_key: &str, secret_key: &str, region: &str, service: &str) -> Self {
+        SigV4Signer {
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+            session_token: None,
+            region: region.to_string(),
+            service: service.to_string()
+        }
+    }
+
+    pub fn session_token(mut self, session_token: &str) -> Self {
+        self.session_token = Some(session_token.to_string());
+        self
+    }
+
+    pub fn sign_at(&self, request: &mut SignableRequest, time: SystemTime) -> ClientixResult<()> {
+        let (date_time, date) = amz_date(time);
+
+        request.set_header(AMZ_DATE_HEADER, date_time.as_str(), false)?;
+
+        if self.service == S3_SERVICE && !request.headers().contains_key(AMZ_CONTENT_SHA256_HEADER) {
+            let payload_hash = request.payload_hash();
+            request.set_header(AMZ_CONTENT_SHA256_HEADER, payload_hash.as_str(), false)?;
+        }
+
+        if let Some(session_token) = &self.session_token {
+            request.set_header(AMZ_SECURITY_TOKEN_HEADER, session_token.as_str(), true)?;
+        }
+
+        let canonical_request = request.canonical_request(self.canonical_path(request).as_str(), &signed_headers(request));
+        let scope = format!("{date}/{}/{}/{TERMINATOR}", self.region, self.service);
+        let string_to_sign = format!(
+            "{ALGORITHM}\n{date_time}\n{scope}\n{}",
+            hex_encode(Sha256::digest(canonical_request.as_bytes()).as_slice())
+        );
+
+        let signing_key = [self.region.as_str(), self.service.as_str(), TERMINATOR].iter()
+            .fold(hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), date.as_bytes()), |key, part| hmac_sha256(&key, part.as_bytes()));
+        let signature = hex_encode(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={}, Signature={signature}",
+            self.access_key,
+            signed_headers(request).join(";")
+        );
+
+        request.set_header(AUTHORIZATION.as_str(), authorization.as_str(), true)
+    }
+
+    // S3 signs the path as sent, other services encode each segment once more
+    fn canonical_path(&self, request: &SignableRequest) -> String {
+        let path = request.url().path();
+
+        match self.service.as_str() {
+            S3_SERVICE => path.to_string(),
+            _ => uri_encode(path, false)
+        }
+    }
+
+}
+
+impl ClientixSigner for SigV4Signer {
+
+    fn sign(&self, request: &mut SignableRequest) -> ClientixResult<()> {
+        self.sign_at(request, SystemTime::now())
+    }
+
+}
+
+fn signed_headers(request: &SignableRequest) -> Vec<String> {
+    let mut signed_headers = request.headers().keys()
+        .map(|name| name.as_str())
+        .filter(|name| name.starts_with(AMZ_HEADER_PREFIX) || [CONTENT_TYPE.as_str(), CONTENT_MD5_HEADER, RANGE.as_str()].contains(name))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+    signed_headers.push(HOST.to_string());
+    signed_headers.sort();
+    signed_headers.dedup();
+
+    signed_headers
+}
+
+// formats as 20150830T123600Z and 20150830
+fn amz_date(time: SystemTime) -> (String, String) {
+    let seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let days = days as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    let date = format!("{year:04}{month:02}{day:02}");
+    let date_time = format!("{date}T{:02}{:02}{:02}Z", seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    (date_time, date)
+}
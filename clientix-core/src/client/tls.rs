@@ -0,0 +1,211 @@
+use std::sync::Arc;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use sha2::{Digest, Sha256};
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const ALPN_PROTOCOLS: [&[u8]; 2] = [b"h2", b"http/1.1"];
+
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    root_certificates: Vec<Vec<u8>>,
+    built_in_roots: bool,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    pins: Vec<String>,
+    danger_accept_invalid_certs: bool
+}
+
+#[derive(Debug)]
+struct ClientixCertVerifier {
+    inner: Option<Arc<WebPkiServerVerifier>>,
+    pins: Vec<[u8; 32]>,
+    provider: Arc<CryptoProvider>
+}
+
+impl Default for TlsConfig {
+
+    fn default() -> Self {
+        TlsConfig {
+            root_certificates: vec![],
+            built_in_roots: true,
+            identity: None,
+            pins: vec![],
+            danger_accept_invalid_certs: false
+        }
+    }
+
+}
+
+impl TlsConfig {
+
+    pub fn add_root_certificate(&mut self, pem: &[u8]) {
+        self.root_certificates.push(pem.to_vec());
+    }
+
+    pub fn set_built_in_roots(&mut self, enabled: bool) {
+        self.built_in_roots = enabled;
+    }
+
+    pub fn set_identity(&mut self, certificate_pem: &[u8], key_pem: &[u8]) {
+        self.identity = Some((certificate_pem.to_vec(), key_pem.to_vec()));
+    }
+
+    pub fn add_pin(&mut self, fingerprint: &str) {
+        self.pins.push(fingerprint.to_string());
+    }
+
+    pub fn set_danger_accept_invalid_certs(&mut self, enabled: bool) {
+        self.danger_accept_invalid_certs = enabled;
+    }
+
+    // without any customization the transport keeps its default tls stack
+    pub fn is_configured(&self) -> bool {
+        !self.root_certificates.is_empty()
+            || !self.built_in_roots
+            || self.identity.is_some()
+            || !self.pins.is_empty()
+            || self.danger_accept_invalid_certs
+    }
+
+    pub fn rustls_config(&self) -> ClientixResult<rustls::ClientConfig> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let pins = self.pins.iter()
+            .map(|pin| parse_fingerprint(pin))
+            .collect::<ClientixResult<Vec<_>>>()?;
+
+        let inner = match self.danger_accept_invalid_certs {
+            true => None,
+            false => Some(self.server_verifier(provider.clone())?)
+        };
+
+        let verifier = Arc::new(ClientixCertVerifier { inner, pins, provider: provider.clone() });
+
+        let builder = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|err| tls_error("unsupported tls protocol versions".to_string(), Some(err.into())))?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier);
+
+        let mut config = match &self.identity {
+            Some((certificate, key)) => {
+                let certificates = CertificateDer::pem_slice_iter(certificate)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| tls_error("invalid client certificate".to_string(), Some(err.into())))?;
+
+                let key = PrivateKeyDer::from_pem_slice(key)
+                    .map_err(|err| tls_error("invalid client private key".to_string(), Some(err.into())))?;
+
+                builder.with_client_auth_cert(certificates, key)
+                    .map_err(|err| tls_error("invalid client identity".to_string(), Some(err.into())))?
+            },
+            None => builder.with_no_client_auth()
+        };
+
+        config.alpn_protocols = ALPN_PROTOCOLS.iter().map(|protocol| protocol.to_vec()).collect();
+
+        Ok(config)
+    }
+
+    fn server_verifier(&self, provider: Arc<CryptoProvider>) -> ClientixResult<Arc<WebPkiServerVerifier>> {
+        let mut roots = RootCertStore::empty();
+
+        if self.built_in_roots {
+            // unreadable system certificates are skipped, the same way the default tls stack does
+            roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        }
+
+        for pem in self.root_certificates.iter() {
+            for certificate in CertificateDer::pem_slice_iter(pem) {
+                let certificate = certificate
+                    .map_err(|err| tls_error("invalid root certificate".to_string(), Some(err.into())))?;
+
+                roots.add(certificate)
+                    .map_err(|err| tls_error("invalid root certificate".to_string(), Some(err.into())))?;
+            }
+        }
+
+        WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+            .build()
+            .map_err(|err| tls_error("no trusted root certificates".to_string(), Some(err.into())))
+    }
+
+}
+
+impl ServerCertVerifier for ClientixCertVerifier {
+
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(inner) = &self.inner {
+            inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+        }
+
+        // only the leaf is matched, the rest of the chain is whatever the server chose to send
+        let pinned = self.pins.is_empty() || self.pins.contains(&Sha256::digest(end_entity.as_ref()).into());
+
+        match pinned {
+            true => Ok(ServerCertVerified::assertion()),
+            false => Err(rustls::Error::General("server certificate does not match any pinned fingerprint".to_string()))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, certificate, signature, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, certificate, signature, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+
+}
+
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der).iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn parse_fingerprint(fingerprint: &str) -> ClientixResult<[u8; 32]> {
+    let hex = fingerprint.trim().replace(':', "");
+    let invalid = || tls_error(format!("invalid sha-256 fingerprint: {fingerprint}"), None);
+
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let mut bytes = [0u8; 32];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+
+    Ok(bytes)
+}
+
+fn tls_error(message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), source)
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+pub fn parse_duration(value: &str) -> ClientixResult<Duration> {
+    let value = value.trim();
+    let invalid_duration = || ClientixError::Other(
+        ClientixErrorData::builder()
+            .message(format!("invalid duration: {value:?}, expected e.g. 500ms, 30s, 2m, 1h").as_str())
+            .build(),
+        None
+    );
+
+    if value.is_empty() {
+        return Err(invalid_duration());
+    }
+
+    let mut duration = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.find(|char: char| !char.is_ascii_digit()).unwrap_or(rest.len());
+        let amount = rest[..digits].parse::<u64>().map_err(|_| invalid_duration())?;
+        rest = &rest[digits..];
+
+        let unit = rest.find(|char: char| char.is_ascii_digit()).unwrap_or(rest.len());
+        let part = match &rest[..unit] {
+            "ms" => Some(Duration::from_millis(amount)),
+            "s" => Some(Duration::from_secs(amount)),
+            "m" => amount.checked_mul(60).map(Duration::from_secs),
+            "h" => amount.checked_mul(60 * 60).map(Duration::from_secs),
+            _ => return Err(invalid_duration())
+        };
+        duration = part.and_then(|part| duration.checked_add(part)).ok_or_else(invalid_duration)?;
+        rest = &rest[unit..];
+    }
+
+    Ok(duration)
+}
@@ -0,0 +1,49 @@
+use std::env;
+use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+const INTERPOLATION_START: &str = "${";
+const INTERPOLATION_END: char = '}';
+const DEFAULT_SEPARATOR: &str = ":-";
+
+pub fn interpolate(value: &str) -> ClientixResult<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find(INTERPOLATION_START) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + INTERPOLATION_START.len()..];
+
+        let end = rest.find(INTERPOLATION_END).ok_or_else(|| config_error(format!("unclosed variable in {value:?}")))?;
+        let expression = &rest[..end];
+        rest = &rest[end + 1..];
+
+        let (name, default_value) = match expression.split_once(DEFAULT_SEPARATOR) {
+            Some((name, default_value)) => (name, Some(default_value)),
+            None => (expression, None)
+        };
+
+        match (env::var(name), default_value) {
+            (Ok(variable), _) if !variable.is_empty() => result.push_str(variable.as_str()),
+            (_, Some(default_value)) => result.push_str(default_value),
+            (Ok(variable), None) => result.push_str(variable.as_str()),
+            (Err(_), None) => return Err(config_error(format!("missing environment variable: {name}")))
+        }
+    }
+
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+// interpolates a value for the generated client builders: the first error is kept in error and an empty string
+// returned, so a missing variable fails the builder's setup instead of panicking while it is being created
+pub fn resolve(value: &str, error: &mut Option<ClientixError>) -> String {
+    interpolate(value).unwrap_or_else(|err| {
+        error.get_or_insert(err);
+        String::new()
+    })
+}
+
+fn config_error(message: String) -> ClientixError {
+    ClientixError::Config(ClientixErrorData::builder().message(message.as_str()).build(), None)
+}
@@ -50,4 +50,97 @@ pub mod content_type {
         }
     }
     
-}
\ No newline at end of file
+}
+
+pub mod content_encoding {
+    use std::fmt::Display;
+    use std::io::Write;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use crate::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+
+    const BROTLI_BUFFER_SIZE: usize = 4096;
+    const BROTLI_QUALITY: u32 = 5;
+    const BROTLI_WINDOW: u32 = 22;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ContentEncoding {
+        Gzip,
+        Deflate,
+        Brotli,
+        Zstd
+    }
+
+    impl ContentEncoding {
+
+        pub fn encode(&self, data: &[u8]) -> ClientixResult<Vec<u8>> {
+            let encoded = match self {
+                ContentEncoding::Gzip => {
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(data).and_then(|_| encoder.finish())
+                },
+                ContentEncoding::Deflate => {
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(data).and_then(|_| encoder.finish())
+                },
+                ContentEncoding::Brotli => {
+                    let mut encoded = Vec::new();
+                    let mut encoder = brotli::CompressorWriter::new(&mut encoded, BROTLI_BUFFER_SIZE, BROTLI_QUALITY, BROTLI_WINDOW);
+                    let result = encoder.write_all(data).and_then(|_| encoder.flush());
+                    drop(encoder);
+
+                    result.map(|_| encoded)
+                },
+                ContentEncoding::Zstd => zstd::stream::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL)
+            };
+
+            encoded.map_err(|err| ClientixError::InvalidRequest(
+                ClientixErrorData::builder().message(format!("failed to {self} encode request body").as_str()).build(),
+                Some(err.into())
+            ))
+        }
+
+    }
+
+    impl TryFrom<String> for ContentEncoding {
+        type Error = ();
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            match value.as_str() {
+                "gzip" => Ok(ContentEncoding::Gzip),
+                "deflate" => Ok(ContentEncoding::Deflate),
+                "br" | "brotli" => Ok(ContentEncoding::Brotli),
+                "zstd" => Ok(ContentEncoding::Zstd),
+                _ => Err(())
+            }
+        }
+    }
+
+    impl Display for ContentEncoding {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let str = match self {
+                ContentEncoding::Gzip => "gzip",
+                ContentEncoding::Deflate => "deflate",
+                ContentEncoding::Brotli => "br",
+                ContentEncoding::Zstd => "zstd"
+            };
+
+            write!(f, "{}", str)
+        }
+    }
+
+}
+
+pub mod idempotency_key {
+
+    pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+    // a random UUID (version 4), one per logical call
+    pub fn generate() -> String {
+        let value = (rand::random::<u128>() & !(0xf << 76) & !(0x3 << 62)) | (0x4 << 76) | (0x2 << 62);
+        let hex = format!("{value:032x}");
+
+        format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+    }
+
+}
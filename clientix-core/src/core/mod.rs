@@ -1 +1,4 @@
-pub mod headers;
\ No newline at end of file
+pub mod duration;
+pub mod env;
+pub mod headers;
+pub mod query;
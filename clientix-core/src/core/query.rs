@@ -0,0 +1,57 @@
+pub mod style {
+    use std::fmt::Display;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum QueryStyle {
+        #[default]
+        Repeat,
+        Comma,
+        Brackets
+    }
+
+    impl QueryStyle {
+
+        pub fn format(&self, key: &str, values: Vec<String>) -> Vec<(String, String)> {
+            if values.is_empty() {
+                return vec![];
+            }
+
+            match self {
+                QueryStyle::Repeat => values.into_iter()
+                    .map(|value| (key.to_string(), value))
+                    .collect(),
+                QueryStyle::Comma => vec![(key.to_string(), values.join(","))],
+                QueryStyle::Brackets => values.into_iter()
+                    .map(|value| (format!("{key}[]"), value))
+                    .collect(),
+            }
+        }
+
+    }
+
+    impl TryFrom<String> for QueryStyle {
+        type Error = ();
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            match value.as_str() {
+                "repeat" => Ok(QueryStyle::Repeat),
+                "comma" => Ok(QueryStyle::Comma),
+                "brackets" => Ok(QueryStyle::Brackets),
+                _ => Err(())
+            }
+        }
+    }
+
+    impl Display for QueryStyle {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let str = match self {
+                QueryStyle::Repeat => "repeat",
+                QueryStyle::Comma => "comma",
+                QueryStyle::Brackets => "brackets"
+            };
+
+            write!(f, "{}", str)
+        }
+    }
+
+}
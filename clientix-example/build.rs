@@ -0,0 +1,20 @@
+use std::env;
+use std::path::Path;
+use clientix_openapi::Generator;
+
+const SPEC: &str = "openapi.yaml";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SPEC}");
+
+    let client = Generator::from_path(SPEC)
+        .and_then(|generator| generator.asynchronous(true).generate())
+        .expect("failed to generate the catalog client");
+
+    for warning in client.warnings() {
+        println!("cargo:warning={warning}");
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+    client.write(Path::new(&out_dir).join("catalog_client.rs")).expect("failed to write the catalog client");
+}
@@ -1,8 +1,11 @@
+use clientix::prelude::reqwest::header::HeaderMap;
+use clientix::client::asynchronous::ClientixPageStream;
 use clientix::client::response::{ClientixResponse, ClientixResult};
-use clientix::{clientix, get, post};
-use crate::dto::{CreateObjectRequest, CreatedObjectResponse};
+use clientix::{clientix, get, header, paginated, post};
+use crate::dto::{CreateObjectRequest, CreatedObjectResponse, ObjectFilter, ObjectSummary};
 
-#[clientix(url = "https://api.restful-api.dev", async = true)]
+#[clientix(url = "${CLIENTIX_EXAMPLE_URL:-https://api.restful-api.dev}", async = true, user_agent = "clientix-example", timeout = "30s", connect_timeout = "5s")]
+#[header(name = "X-Client", value = "clientix-example")]
 pub trait AsyncExampleClient {
 
     #[get(path = "/objects")]
@@ -24,4 +27,68 @@ pub trait AsyncExampleClient {
     #[post(path = "/objects")]
     async fn post(&self, #[body] request: CreateObjectRequest) -> ClientixResult<ClientixResponse<CreatedObjectResponse>>;
 
-}
\ No newline at end of file
+    #[post(path = "/objects/bulk", compress = "zstd")]
+    async fn post_bulk(&self, #[body] request: CreateObjectRequest) -> ClientixResult<ClientixResponse<String>>;
+
+    #[get(path = "/objects/slow", max_in_flight = 1, limit_mode = "fail")]
+    async fn get_slow(&self) -> ClientixResult<String>;
+
+    #[get(path = "/objects/hedged", hedge = "50ms")]
+    async fn get_hedged(&self) -> ClientixResult<String>;
+
+    #[get(path = "/objects")]
+    async fn search(
+        &self,
+        #[query] name: Option<&str>,
+        #[query(style = "comma")] ids: Vec<u32>,
+        #[query(name = "tag", style = "brackets")] tags: &[String],
+        #[query(default_value = "10")] limit: Option<u32>,
+        #[query(flatten)] filter: ObjectFilter
+    ) -> ClientixResult<ClientixResponse<String>>;
+
+    #[paginated(strategy = "cursor", cursor = "meta.next", items = "data")]
+    #[get(path = "/objects/{kind}")]
+    async fn list_objects(&self, #[segment] kind: &str, #[query] color: &str) -> ClientixPageStream<ObjectSummary>;
+
+    #[header(name = "Accept-Language", value = "en")]
+    #[get(path = "/objects/{id}")]
+    async fn get_object(
+        &self,
+        #[segment] id: &str,
+        #[header(name = "X-Request-Id")] request_id: &str,
+        #[header(name = "X-Tenant")] tenant: Option<String>,
+        #[header(name = "X-Api-Key", sensitive = true)] api_key: &str,
+        #[headers] extra_headers: &HeaderMap
+    ) -> ClientixResult<ClientixResponse<String>>;
+
+}
+
+#[clientix(url = "https://api.restful-api.dev", async = true, api_key(in = "cookie", name = "session", value = "${CLIENTIX_EXAMPLE_API_KEY:-secret}"))]
+pub trait AsyncApiKeyClient {
+
+    #[get(path = "/objects")]
+    async fn get_objects(&self, #[header(name = "Cookie")] cookie: &str) -> ClientixResult<String>;
+
+}
+
+#[clientix(urls = ["http://127.0.0.1:8081", "http://127.0.0.1:8082"], load_balancing = "least_outstanding", async = true)]
+pub trait AsyncReplicaClient {
+
+    #[get(path = "/objects")]
+    async fn get_objects(&self) -> ClientixResult<String>;
+
+    #[post(path = "/objects")]
+    async fn post_object(&self, #[body] request: CreateObjectRequest) -> ClientixResult<String>;
+
+    #[post(path = "/payments", idempotency_key = true)]
+    async fn create_payment(&self, #[idempotency_key] key: Option<String>, #[body] request: CreateObjectRequest) -> ClientixResult<String>;
+
+}
+
+#[clientix(url = "http://10.255.255.1", async = true, read_timeout = "300ms", connect_timeout = "200ms")]
+pub trait AsyncTimeoutClient {
+
+    #[get(path = "/objects")]
+    async fn get_objects(&self, #[query] delay_ms: u64) -> ClientixResult<String>;
+
+}
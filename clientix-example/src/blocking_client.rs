@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+use clientix::client::blocking::ClientixPageIterator;
 use clientix::client::response::{ClientixResponse, ClientixResult};
-use clientix::{clientix, get, post};
-use crate::dto::{CreateObjectRequest, CreatedObjectResponse};
+use clientix::{clientix, get, header, paginated, post};
+use crate::dto::{CreateObjectRequest, CreatedObjectResponse, ObjectFilter, ObjectSummary};
 
-#[clientix(url = "https://api.restful-api.dev")]
+#[clientix(url = "https://api.restful-api.dev", user_agent = "clientix-example", timeout = "30s", connect_timeout = "5s")]
+#[header(name = "X-Client", value = "clientix-example")]
 pub trait BlockingExampleClient {
     
     #[get(path = "/objects")]
@@ -24,4 +27,80 @@ pub trait BlockingExampleClient {
     #[post(path = "/objects")]
     fn post(&self, #[body] request: CreateObjectRequest) -> ClientixResult<ClientixResponse<CreatedObjectResponse>>;
 
-}
\ No newline at end of file
+    #[post(path = "/objects/bulk", compress = "gzip")]
+    fn post_bulk(&self, #[body] request: CreateObjectRequest) -> ClientixResult<ClientixResponse<String>>;
+
+    #[post(path = "/payments", idempotency_key = true)]
+    fn create_payment(&self, #[body] request: CreateObjectRequest) -> ClientixResult<String>;
+
+    #[post(path = "/payments")]
+    fn create_payment_with_key(&self, #[idempotency_key] key: &str, #[body] request: CreateObjectRequest) -> ClientixResult<String>;
+
+    #[get(path = "/objects")]
+    fn search(
+        &self,
+        #[query] name: Option<&str>,
+        #[query(style = "comma")] ids: Vec<u32>,
+        #[query(name = "tag", style = "brackets")] tags: &[String],
+        #[query(default_value = "10")] limit: Option<u32>,
+        #[query(flatten)] filter: ObjectFilter
+    ) -> ClientixResult<ClientixResponse<String>>;
+
+    #[paginated(strategy = "link")]
+    #[get(path = "/objects/pages")]
+    fn list_objects(&self, #[query] color: &str) -> ClientixPageIterator<ObjectSummary>;
+
+    #[paginated(strategy = "offset", page_size = 2, items = "data")]
+    #[get(path = "/objects/{kind}")]
+    fn list_objects_by_offset(&self, #[segment] kind: &str, #[header(name = "X-Tenant")] tenant: &str) -> ClientixPageIterator<ObjectSummary>;
+
+    #[header(name = "Accept-Language", value = "en")]
+    #[get(path = "/objects/{id}")]
+    fn get_object(
+        &self,
+        #[segment] id: &str,
+        #[header(name = "X-Request-Id")] request_id: &str,
+        #[header(name = "X-Tenant")] tenant: Option<String>,
+        #[header(name = "X-Api-Key", sensitive = true)] api_key: &str,
+        #[headers] extra_headers: HashMap<String, String>
+    ) -> ClientixResult<ClientixResponse<String>>;
+
+}
+
+#[clientix(url = "https://api.restful-api.dev", api_key(in = "query", name = "api_key"))]
+pub trait BlockingApiKeyClient {
+
+    #[get(path = "/objects")]
+    fn get_objects(&self, #[query] limit: u32) -> ClientixResult<String>;
+
+}
+
+#[clientix(url = "https://api.restful-api.dev", cookie_store = true)]
+pub trait BlockingSessionClient {
+
+    #[post(path = "/login")]
+    fn login(&self) -> ClientixResult<String>;
+
+    #[get(path = "/renew")]
+    fn renew(&self) -> ClientixResult<String>;
+
+    #[get(path = "/me")]
+    fn me(&self, #[cookie(name = "theme")] theme: Option<&str>) -> ClientixResult<String>;
+
+}
+
+#[clientix(url = "${CLIENTIX_EXAMPLE_ENV_URL}", api_key(in = "header", name = "X-Api-Key", value = "${CLIENTIX_EXAMPLE_ENV_API_KEY:-fallback}"))]
+pub trait BlockingEnvClient {
+
+    #[get(path = "/objects")]
+    fn get_objects(&self) -> ClientixResult<String>;
+
+}
+
+#[clientix(url = "http://10.255.255.1", timeout = "300ms", connect_timeout = "200ms")]
+pub trait BlockingTimeoutClient {
+
+    #[get(path = "/objects")]
+    fn get_objects(&self, #[query] delay_ms: u64) -> ClientixResult<String>;
+
+}
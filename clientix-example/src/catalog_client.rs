@@ -0,0 +1,2 @@
+// generated by build.rs from openapi.yaml
+include!(concat!(env!("OUT_DIR"), "/catalog_client.rs"));
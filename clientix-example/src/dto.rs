@@ -12,4 +12,16 @@ pub struct CreatedObjectResponse {
     pub id: String,
     pub name: String,
     pub data: HashMap<String, String>
+}
+
+#[data_transfer]
+pub struct ObjectFilter {
+    pub color: Option<String>,
+    pub capacity: u32
+}
+
+#[data_transfer]
+pub struct ObjectSummary {
+    pub id: String,
+    pub name: String
 }
\ No newline at end of file
@@ -1,13 +1,56 @@
+/// Hedging needs an async client, so a hedged method on a blocking client does not compile:
+///
+/// ```compile_fail
+/// use clientix::{clientix, get};
+/// use clientix::client::response::ClientixResult;
+///
+/// #[clientix(url = "http://localhost")]
+/// trait BlockingHedgedClient {
+///     #[get(path = "/objects", hedge = "50ms")]
+///     fn get_objects(&self) -> ClientixResult<String>;
+/// }
+/// ```
 mod blocking_client;
 mod dto;
 mod async_client;
+#[allow(dead_code)]
+mod catalog_client;
+
+#[cfg(test)]
+mod server;
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use crate::async_client::AsyncExampleClient;
-    use crate::blocking_client::BlockingExampleClient;
-    use crate::dto::CreateObjectRequest;
+    use crate::async_client::{AsyncApiKeyClient, AsyncExampleClient, AsyncReplicaClient, AsyncTimeoutClient};
+    use crate::catalog_client::{CatalogClient, CreateProductRequest, Currency};
+    use crate::blocking_client::{BlockingApiKeyClient, BlockingEnvClient, BlockingExampleClient, BlockingSessionClient, BlockingTimeoutClient};
+    use crate::dto::{CreateObjectRequest, ObjectFilter};
+    use crate::server::{RecordedRequest, TestCertificate, TestProxy, TestResponse, TestServer};
+    use clientix::client::auth::{AuthRequest, ClientixAuthProvider};
+    use clientix::client::auth::oauth2::OAuth2Provider;
+    use clientix::client::balancer::BalanceStrategy;
+    use clientix::client::breaker::{CircuitBreakerConfig, CircuitState};
+    use clientix::client::cache::CacheStatus;
+    use clientix::client::cache::disk::DiskCache;
+    use clientix::client::cache::memory::MemoryCache;
+    use clientix::client::Clientix;
+    use clientix::client::cookie::ClientixCookieJar;
+    use clientix::client::request::ClientixRequestBuilder;
+    use clientix::client::limiter::RateLimitConfig;
+    use clientix::client::response::{ClientixError, ClientixErrorData, ClientixResult};
+    use clientix::client::signer::{ClientixSigner, SignableRequest};
+    use clientix::client::signer::sigv4::SigV4Signer;
+    use clientix::client::proxy::ClientixProxy;
+    use clientix::client::redirect::{RedirectAction, RedirectPolicy};
+    use clientix::client::resolver::ClientixResolver;
+    use clientix::client::tls::certificate_fingerprint;
+    use clientix::core::headers::content_encoding::ContentEncoding;
+    use clientix::prelude::futures_util::future::BoxFuture;
+    use clientix::prelude::futures_util::StreamExt;
+    use clientix::prelude::serde_json;
+    use clientix::prelude::reqwest::header::{HeaderMap, HeaderValue};
+    use clientix::prelude::reqwest::{Method, Version};
 
     #[test]
     fn blocking_get_result_full_response_string_test() {
@@ -169,5 +212,1538 @@ mod tests {
         }
     }
 
-}
+    #[test]
+    fn blocking_search_query_test() {
+        let server = TestServer::echo();
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        let filter = ObjectFilter { color: None, capacity: 64 };
+        let tags = vec!["new".to_string(), "sale".to_string()];
+
+        let result = client.search(Some("phone"), vec![1, 2, 3], &tags, None, filter);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            server.last_request().query(),
+            "name=phone&ids=1%2C2%2C3&tag%5B%5D=new&tag%5B%5D=sale&limit=10&capacity=64"
+        );
+    }
+
+    #[tokio::test]
+    async fn async_search_query_test() {
+        let server = TestServer::echo();
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+
+        let filter = ObjectFilter { color: Some("red".to_string()), capacity: 128 };
+
+        let result = client.search(None, vec![], &[], Some(5), filter).await;
+
+        assert!(result.is_ok());
+        assert_eq!(server.last_request().query(), "limit=5&color=red&capacity=128");
+    }
+
+    #[test]
+    fn blocking_get_object_headers_test() {
+        let server = TestServer::echo();
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        let mut extra_headers = HashMap::new();
+        extra_headers.insert("X-Trace".to_string(), "trace-1".to_string());
+
+        let result = client.get_object("42", "request-1", None, "secret", extra_headers);
+
+        assert!(result.is_ok());
+
+        let request = server.last_request();
+        assert_eq!(request.target, "/objects/42");
+        assert_eq!(request.header("X-Request-Id"), Some("request-1"));
+        assert_eq!(request.header("request_id"), None);
+        assert_eq!(request.header("X-Tenant"), None);
+        assert_eq!(request.header("X-Api-Key"), Some("secret"));
+        assert_eq!(request.header("X-Trace"), Some("trace-1"));
+        assert_eq!(request.header("Accept-Language"), Some("en"));
+        assert_eq!(request.header("User-Agent"), Some("clientix-example"));
+        assert_eq!(request.header("X-Client"), Some("clientix-example"));
+    }
+
+    #[tokio::test]
+    async fn async_get_object_headers_test() {
+        let server = TestServer::echo();
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+
+        let mut extra_headers = HeaderMap::new();
+        extra_headers.insert("X-Trace", HeaderValue::from_static("trace-2"));
+
+        let result = client.get_object("42", "request-2", Some("tenant".to_string()), "secret", &extra_headers).await;
+
+        assert!(result.is_ok());
+
+        let request = server.last_request();
+        assert_eq!(request.header("X-Request-Id"), Some("request-2"));
+        assert_eq!(request.header("X-Tenant"), Some("tenant"));
+        assert_eq!(request.header("X-Trace"), Some("trace-2"));
+        assert_eq!(request.header("Accept-Language"), Some("en"));
+        assert_eq!(request.header("User-Agent"), Some("clientix-example"));
+        assert_eq!(request.header("X-Client"), Some("clientix-example"));
+    }
+
+    #[test]
+    fn blocking_from_config_test() {
+        let server = TestServer::echo();
+
+        let config_path = std::env::temp_dir().join(format!("clientix-example-{}.toml", std::process::id()));
+        let config = format!(r#"
+            [clients.objects]
+            url = "{}"
+            timeout = "10s"
+
+            [clients.objects.headers]
+            X-Environment = "test"
+
+            [clients.objects.auth]
+            type = "bearer"
+            token = "${{CLIENTIX_EXAMPLE_MISSING_TOKEN:-token}}"
+        "#, server.url());
+        std::fs::write(&config_path, config).unwrap();
+
+        let client = BlockingExampleClient::config()
+            .from_config(config_path.to_str().unwrap(), "clients.objects")
+            .unwrap()
+            .setup();
+
+        std::fs::remove_file(&config_path).unwrap();
+
+        let result = client.get_result_string();
+
+        assert!(result.is_ok());
+
+        let request = server.last_request();
+        assert_eq!(request.header("X-Environment"), Some("test"));
+        assert_eq!(request.header("Authorization"), Some("Bearer token"));
+        assert_eq!(request.header("X-Client"), Some("clientix-example"));
+    }
+
+    fn token_server(expires_in: u64) -> TestServer {
+        TestServer::start(move |request| {
+            if request.target.starts_with("/token") {
+                let body = String::from_utf8_lossy(&request.body);
+                let token = if body.contains("grant_type=refresh_token") { "refreshed" } else { "issued" };
+
+                return TestResponse::ok(format!(
+                    r#"{{"access_token":"{token}","token_type":"bearer","expires_in":{expires_in},"refresh_token":"refresh-{token}"}}"#
+                ).as_str());
+            }
+
+            match request.header("Authorization") {
+                Some(_) => TestResponse::ok("ok"),
+                None => TestResponse::status(401, ""),
+            }
+        })
+    }
+
+    fn token_requests(server: &TestServer) -> Vec<RecordedRequest> {
+        server.requests().into_iter().filter(|request| request.target == "/token").collect()
+    }
+
+    #[test]
+    fn blocking_oauth2_retry_on_unauthorized_test() {
+        let server = TestServer::start(|request| {
+            if request.target == "/token" {
+                let body = String::from_utf8_lossy(&request.body);
+                let token = if body.contains("grant_type=refresh_token") { "refreshed" } else { "rejected" };
+
+                return TestResponse::ok(format!(r#"{{"access_token":"{token}","expires_in":3600,"refresh_token":"refresh-1"}}"#).as_str());
+            }
+
+            match request.header("Authorization") {
+                Some("Bearer refreshed") => TestResponse::ok("ok"),
+                _ => TestResponse::status(401, ""),
+            }
+        });
+
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client")
+            .client_secret("secret")
+            .scope("objects")
+            .build();
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .auth_provider(provider)
+            .setup();
+
+        assert!(client.get_result_string().is_ok());
+        assert!(client.get_result_string().is_ok());
+
+        let token_requests = token_requests(&server);
+        assert_eq!(token_requests.len(), 2);
+        assert_eq!(
+            String::from_utf8_lossy(&token_requests[0].body),
+            "grant_type=client_credentials&client_id=client&client_secret=secret&scope=objects"
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&token_requests[1].body),
+            "grant_type=refresh_token&refresh_token=refresh-1&client_id=client&client_secret=secret&scope=objects"
+        );
+        assert_eq!(server.last_request().header("Authorization"), Some("Bearer refreshed"));
+    }
+
+    #[test]
+    fn blocking_oauth2_keeps_newer_token_on_stale_unauthorized_test() {
+        let server = token_server(3600);
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client").build();
+        let request = AuthRequest::new(Method::GET, clientix::prelude::reqwest::Url::parse(server.url().as_str()).unwrap());
+
+        let issued = provider.authorize_blocking(&request).unwrap();
+        assert_eq!(issued, Some(HeaderValue::from_static("Bearer issued")));
+
+        // a 401 to a request sent with an older token leaves the current one cached
+        let stale = request.clone().with_authorization(Some(HeaderValue::from_static("Bearer outdated")));
+        assert!(provider.unauthorized(&stale, &HeaderMap::new()));
+        assert_eq!(provider.authorize_blocking(&request).unwrap(), issued);
+        assert_eq!(token_requests(&server).len(), 1);
+
+        let current = request.clone().with_authorization(issued);
+        assert!(provider.unauthorized(&current, &HeaderMap::new()));
+        assert_eq!(provider.authorize_blocking(&request).unwrap(), Some(HeaderValue::from_static("Bearer refreshed")));
+        assert_eq!(token_requests(&server).len(), 2);
+    }
+
+    fn tls_token_server(authority: &TestCertificate) -> TestServer {
+        TestServer::start_tls(&authority.issue("127.0.0.1"), None, |request| {
+            if request.target == "/token" {
+                return TestResponse::ok(r#"{"access_token":"issued","expires_in":3600}"#);
+            }
+
+            match request.header("Authorization") {
+                Some("Bearer issued") => TestResponse::ok("ok"),
+                _ => TestResponse::status(401, ""),
+            }
+        })
+    }
+
+    #[test]
+    fn blocking_oauth2_client_transport_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = tls_token_server(&authority);
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client").build();
+
+        // the token endpoint is only trusted through the root certificate of the client
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_built_in_roots(false)
+            .auth_provider(provider)
+            .setup();
+
+        assert_eq!(client.get_result_string().unwrap(), "ok");
+        assert_eq!(token_requests(&server).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn async_oauth2_client_transport_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = tls_token_server(&authority);
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client").build();
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_built_in_roots(false)
+            .auth_provider(provider)
+            .setup();
+
+        assert_eq!(client.get_result_string().await.unwrap(), "ok");
+        assert_eq!(token_requests(&server).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn async_oauth2_single_flight_test() {
+        let server = token_server(3600);
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client").build();
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .auth_provider(provider)
+            .setup();
+
+        let (first, second, third) = tokio::join!(
+            client.get_result_string(),
+            client.get_result_string(),
+            client.get_result_string()
+        );
+
+        assert!(first.is_ok() && second.is_ok() && third.is_ok());
+        assert_eq!(token_requests(&server).len(), 1);
+        assert_eq!(server.last_request().header("Authorization"), Some("Bearer issued"));
+    }
+
+    #[tokio::test]
+    async fn async_oauth2_refresh_expired_token_test() {
+        let server = token_server(1);
+        let provider = OAuth2Provider::builder(format!("{}/token", server.url()).as_str(), "client").build();
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .auth_provider(provider)
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert!(client.get_result_string().await.is_ok());
+
+        let token_requests = token_requests(&server);
+        assert_eq!(token_requests.len(), 2);
+        assert!(String::from_utf8_lossy(&token_requests[1].body).contains("refresh_token=refresh-issued"));
+        assert_eq!(server.last_request().header("Authorization"), Some("Bearer refreshed"));
+    }
+
+    fn digest_server() -> TestServer {
+        TestServer::start(|request| {
+            match request.header("Authorization") {
+                Some(authorization) if authorization.starts_with(r#"Digest username="user", realm="clientix", nonce="nonce-1""#) => TestResponse::ok("ok"),
+                _ => TestResponse::status(401, "").header(
+                    "WWW-Authenticate",
+                    r#"Digest realm="clientix", qop="auth", algorithm=SHA-256, nonce="nonce-1", opaque="opaque-1""#
+                ),
+            }
+        })
+    }
+
+    #[test]
+    fn blocking_digest_auth_test() {
+        let server = digest_server();
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .digest_auth("user", "password")
+            .setup();
+
+        assert!(client.get_result_string().is_ok());
+        assert!(client.get_result_string().is_ok());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].header("Authorization"), None);
+
+        let first = requests[1].header("Authorization").unwrap();
+        assert!(first.contains("uri=\"/objects\", algorithm=SHA-256"));
+        assert!(first.contains("opaque=\"opaque-1\", qop=auth, nc=00000001"));
+        assert!(requests[2].header("Authorization").unwrap().contains("nc=00000002"));
+    }
+
+    #[tokio::test]
+    async fn async_digest_auth_test() {
+        let server = digest_server();
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .digest_auth("user", "password")
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert!(client.get_result_string().await.is_ok());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].header("Authorization").unwrap().contains("nc=00000002"));
+    }
+
+    #[test]
+    fn blocking_request_digest_auth_test() {
+        let server = digest_server();
+        let client = Clientix::builder().url(server.url().as_str()).build().blocking();
+
+        for _ in 0..2 {
+            let response = client.get().path("/objects").digest_auth("user", "password").send().text();
+            assert!(response.is_ok());
+        }
+
+        // both calls answer the same challenge, the second one without another round trip
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].header("Authorization").unwrap().contains("nc=00000002"));
+    }
+
+    #[test]
+    fn digest_auth_rejected_credentials_test() {
+        let server = TestServer::start(|_| TestResponse::status(401, "").header(
+            "WWW-Authenticate",
+            r#"Digest realm="clientix", qop="auth", nonce="nonce-1""#
+        ));
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .digest_auth("user", "wrong")
+            .setup();
+
+        let result = client.get_result_full_response_string();
+
+        assert_eq!(result.unwrap().status().as_u16(), 401);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    struct RecordingSigner;
+
+    impl ClientixSigner for RecordingSigner {
+        fn sign(&self, request: &mut SignableRequest) -> ClientixResult<()> {
+            let signature = format!("{} {} {}", request.method(), request.url().path(), String::from_utf8_lossy(request.body()));
+            request.set_header("X-Signed", signature.as_str(), false)?;
+            request.set_header("X-Signed-Agent", request.headers()["user-agent"].to_str().unwrap().to_string().as_str(), false)
+        }
+    }
+
+    #[test]
+    fn blocking_signer_test() {
+        let server = TestServer::start(|_| TestResponse::ok(r#"{"id":"1","name":"name","data":{}}"#));
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .signer(RecordingSigner)
+            .setup();
+
+        let request = CreateObjectRequest { name: "name".to_string(), data: HashMap::new() };
+        assert!(client.post(request).is_ok());
+
+        let request = server.last_request();
+        assert_eq!(request.header("X-Signed"), Some(format!("POST /objects {}", String::from_utf8_lossy(&request.body)).as_str()));
+        assert_eq!(request.header("X-Signed-Agent"), Some("clientix-example"));
+    }
+
+    #[tokio::test]
+    async fn async_sigv4_signer_test() {
+        let server = TestServer::echo();
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .signer(SigV4Signer::new("AKIDEXAMPLE", "secret", "us-east-1", "s3"))
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+
+        let request = server.last_request();
+        let authorization = request.header("Authorization").unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        assert!(authorization.contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="));
+        assert!(request.header("x-amz-date").is_some());
+    }
+
+    #[test]
+    fn blocking_api_key_test() {
+        let server = TestServer::echo();
+        let client = BlockingApiKeyClient::config()
+            .url(server.url().as_str())
+            .api_key("secret")
+            .setup();
+
+        assert!(client.get_objects(10).is_ok());
+        assert_eq!(server.last_request().query(), "limit=10&api_key=secret");
+
+        let client = BlockingApiKeyClient::config()
+            .url("http://127.0.0.1:1")
+            .api_key("secret")
+            .setup();
+
+        let error = client.get_objects(10).unwrap_err();
+        assert!(!format!("{error:?}").contains("secret"));
+        assert!(format!("{error:?}").contains("api_key=REDACTED"));
+    }
+
+    #[test]
+    fn blocking_env_interpolation_test() {
+        let result = BlockingEnvClient::config().try_setup();
+        assert!(matches!(result, Err(ClientixError::Config(_, _))));
+
+        let server = TestServer::echo();
+        std::env::set_var("CLIENTIX_EXAMPLE_ENV_URL", server.url());
+        std::env::set_var("CLIENTIX_EXAMPLE_ENV_API_KEY", "from-env");
+
+        let client = BlockingEnvClient::config().try_setup().unwrap();
+
+        assert!(client.get_objects().is_ok());
+        assert_eq!(server.last_request().header("X-Api-Key"), Some("from-env"));
+    }
+
+    #[test]
+    fn blocking_timeout_attribute_test() {
+        let server = TestServer::start(|request| {
+            let delay = request.query().trim_start_matches("delay_ms=").parse::<u64>().unwrap_or_default();
+            std::thread::sleep(std::time::Duration::from_millis(delay));
+            TestResponse::ok("done")
+        });
+
+        let client = BlockingTimeoutClient::config().url(server.url().as_str()).setup();
+        assert_eq!(client.get_objects(0).unwrap(), "done");
+
+        let started = std::time::Instant::now();
+        let result = client.get_objects(3000);
+        assert!(matches!(result, Err(ClientixError::Http(_, _))));
+        assert!(started.elapsed() < std::time::Duration::from_millis(2000));
+
+        // nothing answers on the default url, the connect timeout gives up on it
+        let client = BlockingTimeoutClient::new();
+        let started = std::time::Instant::now();
+        assert!(client.get_objects(0).is_err());
+        assert!(started.elapsed() < std::time::Duration::from_millis(2000));
+
+        // blocking clients only have a total timeout, a read timeout is rejected
+        let result = BlockingTimeoutClient::config()
+            .url(server.url().as_str())
+            .read_timeout(std::time::Duration::from_millis(300))
+            .try_setup();
+        assert!(matches!(result, Err(ClientixError::Config(_, _))));
+    }
+
+    #[tokio::test]
+    async fn async_read_timeout_attribute_test() {
+        let server = TestServer::start(|request| {
+            let delay = request.query().trim_start_matches("delay_ms=").parse::<u64>().unwrap_or_default();
+            std::thread::sleep(std::time::Duration::from_millis(delay));
+            TestResponse::ok("done")
+        });
+
+        let client = AsyncTimeoutClient::config().url(server.url().as_str()).setup();
+        assert_eq!(client.get_objects(0).await.unwrap(), "done");
+
+        let started = std::time::Instant::now();
+        let result = client.get_objects(3000).await;
+        assert!(matches!(result, Err(ClientixError::Http(_, _))));
+        assert!(started.elapsed() < std::time::Duration::from_millis(2000));
+    }
+
+    #[tokio::test]
+    async fn async_api_key_test() {
+        let server = TestServer::echo();
+        let client = AsyncApiKeyClient::config().url(server.url().as_str()).setup();
+
+        assert!(client.get_objects("theme=dark").await.is_ok());
+        assert_eq!(server.last_request().header("Cookie"), Some("theme=dark; session=secret"));
+
+        let client = AsyncApiKeyClient::config().url(server.url().as_str()).api_key("a;b").setup();
+        assert!(client.get_objects("theme=dark").await.is_ok());
+        assert_eq!(server.last_request().header("Cookie"), Some("theme=dark; session=a%3Bb"));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .api_key("header-secret")
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert_eq!(server.last_request().header("X-Api-Key"), Some("header-secret"));
+    }
+
+    fn session_server() -> TestServer {
+        TestServer::start(|request| match request.target.as_str() {
+            "/login" => TestResponse::ok("ok").header("Set-Cookie", "session=abc; Path=/; HttpOnly"),
+            "/renew" => TestResponse::status(302, "").header("Location", "/me").header("Set-Cookie", "session=renewed; Path=/"),
+            _ => TestResponse::ok(request.header("Cookie").unwrap_or_default())
+        })
+    }
+
+    #[test]
+    fn blocking_cookie_store_test() {
+        let server = session_server();
+        let client = BlockingSessionClient::config().url(server.url().as_str()).setup();
+
+        assert_eq!(client.me(None).unwrap(), "");
+        assert_eq!(client.login().unwrap(), "ok");
+        assert_eq!(client.me(None).unwrap(), "session=abc");
+        assert_eq!(client.me(Some("dark")).unwrap(), "session=abc; theme=dark");
+        assert_eq!(client.me(Some("a;b,c")).unwrap(), "session=abc; theme=a%3Bb%2Cc");
+
+        // the cookie set by the redirect is sent on the next hop
+        assert_eq!(client.renew().unwrap(), "session=renewed");
+        assert_eq!(client.login().unwrap(), "ok");
+
+        let cookie_jar = client.cookie_jar().unwrap();
+        assert_eq!(cookie_jar.get("session").map(|cookie| cookie.value), Some("abc".to_string()));
+
+        cookie_jar.add("short=lived; Max-Age=1", server.url().as_str()).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+
+        let cookies_path = std::env::temp_dir().join(format!("clientix-example-cookies-{}.json", std::process::id()));
+        cookie_jar.save(&cookies_path).unwrap();
+        assert!(!std::fs::read_to_string(&cookies_path).unwrap().contains("short"));
+
+        let restored = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .cookie_jar(std::sync::Arc::new(ClientixCookieJar::load(&cookies_path).unwrap()))
+            .setup();
+        std::fs::remove_file(&cookies_path).unwrap();
+
+        assert_eq!(restored.me(None).unwrap(), "session=abc");
+
+        cookie_jar.clear();
+        assert!(cookie_jar.cookies().is_empty());
+        assert_eq!(client.me(None).unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn async_cookie_jar_seed_test() {
+        let server = TestServer::echo();
+        let cookie_jar = std::sync::Arc::new(ClientixCookieJar::new());
+        cookie_jar.add("token=seeded; Path=/objects", server.url().as_str()).unwrap();
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .cookie_jar(cookie_jar.clone())
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert_eq!(server.last_request().header("Cookie"), Some("token=seeded"));
+
+        let server_url = clientix::prelude::reqwest::Url::parse(server.url().as_str()).unwrap();
+        assert!(cookie_jar.remove(server_url.host_str().unwrap(), "/objects", "token"));
+
+        assert!(client.get_result_string().await.is_ok());
+        assert_eq!(server.last_request().header("Cookie"), None);
+    }
+
+    #[test]
+    fn blocking_tls_root_certificate_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = TestServer::start_tls(&authority.issue("127.0.0.1"), None, |_| TestResponse::ok("secure"));
+
+        let client = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_built_in_roots(false)
+            .setup();
+
+        assert_eq!(client.me(None).unwrap(), "secure");
+
+        let untrusted = BlockingSessionClient::config().url(server.url().as_str()).setup();
+
+        assert!(untrusted.me(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_tls_client_identity_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let client_authority = TestCertificate::authority("clientix client ca");
+        let identity = client_authority.issue("clientix-example");
+        let server = TestServer::start_tls(&authority.issue("127.0.0.1"), Some(&client_authority), |_| TestResponse::ok("ok"));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_identity(identity.certificate_pem().as_bytes(), identity.key_pem().as_bytes())
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+
+        let anonymous = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .setup();
+
+        assert!(anonymous.get_result_string().await.is_err());
+    }
+
+    #[test]
+    fn blocking_tls_pin_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let certificate = authority.issue("127.0.0.1");
+        let server = TestServer::start_tls(&certificate, None, |_| TestResponse::ok("pinned"));
+
+        let client = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_pin(certificate_fingerprint(certificate.der()).as_str())
+            .setup();
+
+        assert_eq!(client.me(None).unwrap(), "pinned");
+
+        let mismatched = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_pin(certificate_fingerprint(authority.issue("127.0.0.1").der()).as_str())
+            .setup();
+
+        assert!(mismatched.me(None).is_err());
+    }
+
+    #[test]
+    fn blocking_invalid_tls_configuration_test() {
+        let result = BlockingSessionClient::config().tls_pin("not a fingerprint").try_setup();
+        assert!(matches!(result, Err(ClientixError::Config(_, _))));
+
+        let result = AsyncExampleClient::config()
+            .tls_root_certificate(b"-----BEGIN CERTIFICATE-----\n!!!\n-----END CERTIFICATE-----\n")
+            .try_setup();
+        assert!(matches!(result, Err(ClientixError::Config(_, _))));
+    }
+
+    #[test]
+    fn blocking_tls_pin_appended_certificate_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let pinned = authority.issue("127.0.0.1");
+        let server = TestServer::start_tls_chain(&authority.issue("127.0.0.1"), &[&pinned], None, |_| TestResponse::ok("pinned"));
+
+        // the pinned certificate appended to the chain of another leaf does not count
+        let client = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .tls_pin(certificate_fingerprint(pinned.der()).as_str())
+            .setup();
+
+        assert!(client.me(None).is_err());
+
+        let unverified = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .danger_accept_invalid_certs(true)
+            .tls_pin(certificate_fingerprint(pinned.der()).as_str())
+            .setup();
+
+        assert!(unverified.me(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_tls_danger_accept_invalid_certs_test() {
+        let certificate = TestCertificate::self_signed("localhost");
+        let server = TestServer::start_tls(&certificate, None, |_| TestResponse::ok("ok"));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .danger_accept_invalid_certs(true)
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+
+        let pinned = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .danger_accept_invalid_certs(true)
+            .tls_pin(certificate_fingerprint(TestCertificate::self_signed("localhost").der()).as_str())
+            .setup();
+
+        assert!(pinned.get_result_string().await.is_err());
+    }
+
+    #[test]
+    fn blocking_https_proxy_connect_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = TestServer::start_tls(&authority.issue("127.0.0.1"), None, |_| TestResponse::ok("tunneled"));
+        let proxy = TestProxy::start(true);
+
+        let client = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .proxy(ClientixProxy::https(proxy.url().as_str()).basic_auth("user", "pass"))
+            .setup();
+
+        assert_eq!(client.me(None).unwrap(), "tunneled");
+
+        let connect = proxy.requests().pop().unwrap();
+        assert_eq!(connect.method, "CONNECT");
+        assert_eq!(connect.target, server.url().trim_start_matches("https://"));
+        assert_eq!(connect.header("Proxy-Authorization"), Some("Basic dXNlcjpwYXNz"));
+
+        let anonymous = BlockingSessionClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .proxy(ClientixProxy::https(proxy.url().as_str()))
+            .setup();
+
+        assert!(anonymous.me(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_http_proxy_no_proxy_test() {
+        let server = TestServer::echo();
+        let proxy = TestProxy::start(false);
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .proxy(ClientixProxy::all(proxy.url().as_str()))
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert_eq!(proxy.requests().len(), 1);
+        assert!(server.last_request().target.starts_with(server.url().as_str()));
+
+        let bypassed = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .proxy(ClientixProxy::all(proxy.url().as_str()))
+            .no_proxy("localhost,127.0.0.1")
+            .env_proxy(false)
+            .setup();
 
+        assert!(bypassed.get_result_string().await.is_ok());
+        assert_eq!(proxy.requests().len(), 1);
+        assert!(server.last_request().target.starts_with('/'));
+    }
+
+    fn redirect_server() -> TestServer {
+        TestServer::start(|request| match request.target.as_str() {
+            "/objects/1" => TestResponse::status(302, "").header("Location", "/objects/2"),
+            "/objects/2" => TestResponse::status(307, "").header("Location", "/objects"),
+            _ => TestResponse::ok("landed")
+        })
+    }
+
+    #[test]
+    fn blocking_redirect_history_test() {
+        let server = redirect_server();
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        let response = client.get_object("1", "request-1", None, "secret", HashMap::new()).unwrap();
+        assert_eq!(response.body(), "landed");
+        assert_eq!(response.url().path(), "/objects");
+        assert_eq!(response.redirects().iter().map(|url| url.path()).collect::<Vec<_>>(), vec!["/objects/1", "/objects/2"]);
+        assert_eq!(server.last_request().header("X-Api-Key"), Some("secret"));
+        assert_eq!(server.last_request().header("X-Client"), Some("clientix-example"));
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .redirect(RedirectPolicy::none())
+            .setup();
+
+        let response = client.get_object("1", "request-2", None, "secret", HashMap::new()).unwrap();
+        assert_eq!(response.status().as_u16(), 302);
+        assert_eq!(response.headers().get("Location").unwrap(), "/objects/2");
+        assert!(response.redirects().is_empty());
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .redirect(RedirectPolicy::limited(1))
+            .setup();
+
+        assert!(client.get_object("1", "request-3", None, "secret", HashMap::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_redirect_cross_origin_test() {
+        let target = TestServer::echo();
+        let target_url = format!("{}/landing", target.url());
+        let server = TestServer::start(move |_| TestResponse::status(302, "").header("Location", target_url.as_str()));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .bearer_auth("secret")
+            .setup();
+
+        let response = client.get_result_full_response_string().await.unwrap();
+        assert_eq!(response.url().path(), "/landing");
+        assert_eq!(server.last_request().header("Authorization"), Some("Bearer secret"));
+        assert_eq!(target.last_request().header("Authorization"), None);
+        assert_eq!(target.last_request().header("X-Client"), Some("clientix-example"));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .redirect(RedirectPolicy::same_origin())
+            .setup();
+
+        assert!(client.get_result_full_response_string().await.is_err());
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .redirect(RedirectPolicy::custom(|attempt| match attempt.url().path() {
+                "/landing" => RedirectAction::Stop,
+                _ => RedirectAction::Follow
+            }))
+            .setup();
+
+        let response = client.get_result_full_response_string().await.unwrap();
+        assert_eq!(response.status().as_u16(), 302);
+        assert_eq!(target.requests().len(), 1);
+    }
+
+    fn foreign_redirect_server(target: &TestServer) -> TestServer {
+        let target_url = format!("{}/landing?page=1", target.url());
+        TestServer::start(move |_| TestResponse::status(302, "").header("Location", target_url.as_str()))
+    }
+
+    #[test]
+    fn blocking_redirect_cross_origin_api_key_test() {
+        let target = TestServer::echo();
+        let server = foreign_redirect_server(&target);
+        let client = BlockingApiKeyClient::config()
+            .url(server.url().as_str())
+            .api_key("secret")
+            .setup();
+
+        assert!(client.get_objects(10).is_ok());
+        assert_eq!(server.last_request().query(), "limit=10&api_key=secret");
+        assert_eq!(target.last_request().query(), "page=1");
+    }
+
+    #[tokio::test]
+    async fn async_redirect_cross_origin_api_key_test() {
+        let target = TestServer::echo();
+        let server = foreign_redirect_server(&target);
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .api_key("header-secret")
+            .setup();
+
+        assert!(client.get_result_string().await.is_ok());
+        assert_eq!(server.last_request().header("X-Api-Key"), Some("header-secret"));
+        assert_eq!(target.last_request().header("X-Api-Key"), None);
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+
+        assert!(client.get_object("1", "request-1", Some("tenant".to_string()), "sensitive-secret", &HeaderMap::new()).await.is_ok());
+        assert_eq!(server.last_request().header("X-Api-Key"), Some("sensitive-secret"));
+        assert_eq!(target.last_request().header("X-Api-Key"), None);
+        assert_eq!(target.last_request().header("X-Tenant"), Some("tenant"));
+
+        let client = AsyncApiKeyClient::config().url(server.url().as_str()).setup();
+
+        assert!(client.get_objects("theme=dark").await.is_ok());
+        assert_eq!(target.last_request().header("Cookie"), None);
+    }
+
+    // answers with the request body as is, so an encoded body comes back with the same Content-Encoding
+    fn encoding_echo_server() -> TestServer {
+        TestServer::start(|request| {
+            let response = TestResponse { status: 200, headers: vec![], body: request.body.clone() };
+            match request.header("Content-Encoding") {
+                Some(encoding) => response.header("Content-Encoding", encoding),
+                None => response
+            }
+        })
+    }
+
+    fn bulk_request() -> CreateObjectRequest {
+        CreateObjectRequest { name: "bulk".repeat(64), data: HashMap::new() }
+    }
+
+    #[test]
+    fn blocking_compressed_request_test() {
+        let server = encoding_echo_server();
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        let response = client.post_bulk(bulk_request()).unwrap();
+        let request = server.last_request();
+        assert_eq!(request.header("Content-Encoding"), Some("gzip"));
+        assert_eq!(request.body, ContentEncoding::Gzip.encode(serde_json::to_vec(&bulk_request()).unwrap().as_slice()).unwrap());
+        assert_eq!(response.body(), serde_json::to_string(&bulk_request()).unwrap());
+        assert!(request.header("Accept-Encoding").unwrap().contains("gzip"));
+    }
+
+    #[tokio::test]
+    async fn async_compressed_request_test() {
+        let server = encoding_echo_server();
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+
+        let response = client.post_bulk(bulk_request()).await.unwrap();
+        let request = server.last_request();
+        assert_eq!(request.header("Content-Encoding"), Some("zstd"));
+        assert!(request.body.len() < serde_json::to_vec(&bulk_request()).unwrap().len());
+        assert_eq!(response.body(), serde_json::to_string(&bulk_request()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn async_response_decoding_test() {
+        let body = "analytics ".repeat(100);
+        let encoded = ContentEncoding::Brotli.encode(body.as_bytes()).unwrap();
+        let server = TestServer::start(move |_| TestResponse { status: 200, headers: vec![], body: encoded.clone() }.header("Content-Encoding", "br"));
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+        assert_eq!(client.get_result_string().await.unwrap(), body);
+
+        let accept_encoding = server.last_request().header("Accept-Encoding").unwrap().to_string();
+        for encoding in ["gzip", "br", "deflate", "zstd"] {
+            assert!(accept_encoding.contains(encoding));
+        }
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .gzip(false)
+            .brotli(false)
+            .deflate(false)
+            .zstd(false)
+            .setup();
+
+        assert!(client.get_result_full_response_string().await.is_ok());
+        assert_eq!(server.last_request().header("Accept-Encoding"), None);
+    }
+
+    #[tokio::test]
+    async fn async_http2_prior_knowledge_test() {
+        let server = TestServer::start_h2c(|_| TestResponse::ok("h2c"));
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .http2_prior_knowledge()
+            .pool_idle_timeout(std::time::Duration::from_secs(30))
+            .pool_max_idle_per_host(4)
+            .tcp_keepalive(std::time::Duration::from_secs(60))
+            .tcp_nodelay(true)
+            .setup();
+
+        let response = client.get_result_full_response_string().await.unwrap();
+        assert_eq!(response.version(), Version::HTTP_2);
+        assert_eq!(response.body(), "h2c");
+        assert_eq!(server.last_request().header("x-client"), Some("clientix-example"));
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).http1_only().setup();
+        assert!(client.get_result_full_response_string().await.is_err());
+    }
+
+    #[test]
+    fn blocking_http1_only_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = TestServer::start_tls(&authority.issue("127.0.0.1"), None, |_| TestResponse::ok("ok"));
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .http1_only()
+            .local_address("127.0.0.1".parse().unwrap())
+            .setup();
+
+        let response = client.get_result_full_response_string().unwrap();
+        assert_eq!(response.version(), Version::HTTP_11);
+    }
+
+    #[cfg(unix)]
+    fn unix_socket_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("clientix-example-{name}-{}.sock", std::process::id()))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn blocking_unix_socket_test() {
+        let path = unix_socket_path("blocking");
+        let server = TestServer::start_unix(&path, |request| TestResponse::ok(request.target.as_str()));
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        let response = client.search(Some("phone"), vec![], &[], None, ObjectFilter { color: None, capacity: 2 }).unwrap();
+        assert_eq!(response.body(), "/objects?name=phone&limit=10&capacity=2");
+        assert_eq!(server.last_request().header("Host"), Some("localhost"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn async_unix_socket_test() {
+        let path = unix_socket_path("async");
+        let server = TestServer::start_unix(&path, |request| TestResponse::ok(request.target.as_str()));
+        let client = AsyncExampleClient::config()
+            .url("http://engine")
+            .unix_socket(&path)
+            .setup();
+
+        assert_eq!(client.get_result_string().await.unwrap(), "/objects");
+        assert_eq!(server.last_request().header("Host"), Some("engine"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn blocking_resolve_override_test() {
+        let server = TestServer::echo();
+        let address: std::net::SocketAddr = server.url().trim_start_matches("http://").parse().unwrap();
+
+        let client = BlockingExampleClient::config()
+            .url(format!("http://blue.clientix.test:{}", address.port()).as_str())
+            .resolve("blue.clientix.test", &["127.0.0.1:0".parse().unwrap()])
+            .setup();
+
+        let response = client.get_result_full_response_string().unwrap();
+        assert_eq!(response.remote_addr(), Some(address));
+        assert_eq!(server.last_request().header("Host"), Some(format!("blue.clientix.test:{}", address.port()).as_str()));
+    }
+
+    struct GreenResolver {
+        lookups: std::sync::Arc<std::sync::Mutex<Vec<String>>>
+    }
+
+    impl ClientixResolver for GreenResolver {
+        fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, ClientixResult<Vec<std::net::SocketAddr>>> {
+            Box::pin(async move {
+                self.lookups.lock().unwrap().push(host.to_string());
+
+                match host {
+                    "green.clientix.test" => Ok(vec!["127.0.0.1:0".parse().unwrap()]),
+                    _ => Err(ClientixError::Http(ClientixErrorData::builder().message("unknown host").build(), None))
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn async_resolver_tls_server_name_test() {
+        let authority = TestCertificate::authority("clientix test ca");
+        let server = TestServer::start_tls(&authority.issue("green.clientix.test"), None, |_| TestResponse::ok("green"));
+        let address: std::net::SocketAddr = server.url().trim_start_matches("https://").parse().unwrap();
+        let lookups = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+        // the certificate is only valid for the green name, so the handshake proves the SNI and verified name
+        let client = AsyncExampleClient::config()
+            .url(format!("https://green.clientix.test:{}", address.port()).as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .resolver(GreenResolver { lookups: lookups.clone() })
+            .setup();
+
+        let response = client.get_result_full_response_string().await.unwrap();
+        assert_eq!(response.body(), "green");
+        assert_eq!(response.remote_addr(), Some(address));
+        assert_eq!(server.last_request().header("Host"), Some(format!("green.clientix.test:{}", address.port()).as_str()));
+        assert_eq!(*lookups.lock().unwrap(), vec!["green.clientix.test".to_string()]);
+
+        let client = AsyncExampleClient::config()
+            .url(format!("https://blue.clientix.test:{}", address.port()).as_str())
+            .tls_root_certificate(authority.certificate_pem().as_bytes())
+            .resolver(GreenResolver { lookups: lookups.clone() })
+            .resolve("blue.clientix.test", &[address])
+            .setup();
+
+        assert!(client.get_result_full_response_string().await.is_err());
+        assert_eq!(lookups.lock().unwrap().len(), 1);
+    }
+
+    fn dead_url() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn blocking_load_balancing_failover_test() {
+        let dead = dead_url();
+        let first = TestServer::echo();
+        let second = TestServer::echo();
+
+        let client = BlockingExampleClient::config()
+            .urls(&[dead.as_str(), first.url().as_str(), second.url().as_str()])
+            .load_balancing(BalanceStrategy::RoundRobin)
+            .endpoint_ejection(1, std::time::Duration::from_secs(60))
+            .setup();
+
+        // the dead replica fails over once and is then ejected for the rest of the test
+        for _ in 0..4 {
+            assert_eq!(client.get_result_string().unwrap(), "ok");
+        }
+
+        assert_eq!(first.requests().len() + second.requests().len(), 4);
+        assert!(!first.requests().is_empty());
+        assert!(!second.requests().is_empty());
+        assert_eq!(first.last_request().target, "/objects");
+    }
+
+    #[tokio::test]
+    async fn async_load_balancing_no_failover_for_post_test() {
+        let dead = dead_url();
+        let server = TestServer::echo();
+
+        let client = AsyncReplicaClient::config()
+            .urls(&[dead.as_str(), server.url().as_str()])
+            .load_balancing(BalanceStrategy::RoundRobin)
+            .setup();
+
+        // a post that reached a broken replica is not replayed on another one
+        assert!(client.post_object(bulk_request()).await.is_err());
+        assert!(server.requests().is_empty());
+
+        assert_eq!(client.get_objects().await.unwrap(), "ok");
+        assert_eq!(client.get_objects().await.unwrap(), "ok");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn blocking_circuit_breaker_test() {
+        let server = TestServer::start(|request| match request.target.as_str() {
+            "/objects" => TestResponse { status: 503, headers: vec![], body: b"down".to_vec() },
+            _ => TestResponse::ok("ok")
+        });
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let recorded = events.clone();
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .circuit_breaker(CircuitBreakerConfig::default()
+                .minimum_requests(2)
+                .open_duration(std::time::Duration::from_millis(200))
+                .per_method()
+                .on_state_change(move |event| recorded.lock().unwrap().push((event.circuit().to_string(), event.from(), event.to()))))
+            .setup();
+
+        assert!(client.get_result_string().is_ok());
+        assert!(client.get_result_string().is_ok());
+        assert!(matches!(client.get_result_string(), Err(ClientixError::CircuitOpen(_, _))));
+        assert_eq!(server.requests().len(), 2);
+
+        // circuits are per method, so another method keeps going
+        assert!(client.get_object("1", "request-1", None, "key", HashMap::new()).is_ok());
+
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        assert!(client.get_result_string().is_ok());
+        assert_eq!(server.requests().len(), 4);
+
+        assert_eq!(*events.lock().unwrap(), vec![
+            ("get_result_string".to_string(), CircuitState::Closed, CircuitState::Open),
+            ("get_result_string".to_string(), CircuitState::Open, CircuitState::HalfOpen),
+            ("get_result_string".to_string(), CircuitState::HalfOpen, CircuitState::Open)
+        ]);
+    }
+
+    #[test]
+    fn blocking_rate_limit_wait_test() {
+        let server = TestServer::echo();
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .rate_limit(RateLimitConfig::new().rate(2, std::time::Duration::from_millis(200)))
+            .setup();
+
+        let started = std::time::Instant::now();
+        for _ in 0..4 {
+            assert_eq!(client.get_result_string().unwrap(), "ok");
+        }
+
+        assert!(started.elapsed() >= std::time::Duration::from_millis(180));
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn blocking_rate_limit_retry_after_test() {
+        let server = TestServer::start(|_| TestResponse { status: 429, headers: vec![], body: b"slow down".to_vec() }.header("Retry-After", "30"));
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .rate_limit(RateLimitConfig::new().rate(100, std::time::Duration::from_secs(1)).fail_fast())
+            .setup();
+
+        assert_eq!(client.get_result_full_response_string().unwrap().status().as_u16(), 429);
+        assert!(matches!(client.get_result_string(), Err(ClientixError::RateLimited(_, _))));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn async_max_in_flight_override_test() {
+        let server = TestServer::start(|request| {
+            if request.target == "/objects/slow" {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+            }
+
+            TestResponse::ok("ok")
+        });
+
+        // the client limit does not apply to the method with its own override
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .rate_limit(RateLimitConfig::new().max_in_flight(1).fail_fast())
+            .setup();
+
+        let (first, second, other) = tokio::join!(client.get_slow(), client.get_slow(), client.get_result_string());
+        assert!(first.is_ok() ^ second.is_ok());
+        assert!(first.or(second).is_ok());
+        assert_eq!(other.unwrap(), "ok");
+        assert_eq!(server.requests().iter().filter(|request| request.target == "/objects/slow").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn async_hedged_request_test() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let server = TestServer::start(move |_| match counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+            0 => {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                TestResponse::ok("slow")
+            },
+            _ => TestResponse::ok("fast")
+        });
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+
+        let started = std::time::Instant::now();
+        assert_eq!(client.get_hedged().await.unwrap(), "fast");
+        assert!(started.elapsed() < std::time::Duration::from_millis(400));
+        assert_eq!(server.requests().len(), 2);
+
+        // a quick answer never fires a hedge
+        assert_eq!(client.get_hedged().await.unwrap(), "fast");
+        assert_eq!(server.requests().len(), 3);
+
+        let metrics = client.hedge_metrics("get_hedged");
+        assert_eq!((metrics.requests(), metrics.fired(), metrics.won()), (2, 1, 1));
+    }
+
+
+    #[tokio::test]
+    async fn async_coalescing_test() {
+        let server = TestServer::start(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            TestResponse::ok("shared").header("X-Served", "once")
+        });
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .coalesce(&["Accept-Language"])
+            .setup();
+
+        let (first, second, third) = tokio::join!(
+            client.get_result_full_response_string(),
+            client.get_result_full_response_string(),
+            client.get_result_full_response_string()
+        );
+
+        assert_eq!(server.requests().len(), 1);
+        for response in [first.unwrap(), second.unwrap(), third.unwrap()] {
+            assert_eq!(response.status(), 200);
+            assert_eq!(response.body(), "shared");
+            assert_eq!(response.headers().get("x-served").unwrap(), "once");
+            assert_eq!(response.url().as_str(), format!("{}/objects", server.url()));
+        }
+
+        // coalescing is keyed per generated method and only lasts while the call is in flight
+        let (full, string) = tokio::join!(client.get_result_full_response_string(), client.get_result_string());
+        assert_eq!(full.unwrap().body(), "shared");
+        assert_eq!(string.unwrap(), "shared");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+
+    #[test]
+    fn blocking_cache_fresh_hit_test() {
+        let server = TestServer::start(|_| TestResponse::ok("cached").header("Cache-Control", "max-age=60"));
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .cache(MemoryCache::new())
+            .setup();
+
+        let first = client.get_result_full_response_string().unwrap();
+        assert_eq!(first.cache_status(), Some(CacheStatus::Miss));
+
+        let second = client.get_result_full_response_string().unwrap();
+        assert_eq!(second.cache_status(), Some(CacheStatus::Hit));
+        assert_eq!(second.body(), "cached");
+        assert_eq!(second.url().as_str(), format!("{}/objects", server.url()));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn blocking_cache_revalidation_test() {
+        let server = TestServer::start(|request| match request.header("If-None-Match") {
+            Some("\"v1\"") => TestResponse::status(304, "").header("ETag", "\"v1\"").header("X-Revalidated", "true"),
+            _ => TestResponse::ok("reference").header("Cache-Control", "no-cache").header("ETag", "\"v1\"")
+        });
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .cache(MemoryCache::new())
+            .setup();
+
+        assert_eq!(client.get_result_full_response_string().unwrap().cache_status(), Some(CacheStatus::Miss));
+
+        let revalidated = client.get_result_full_response_string().unwrap();
+        assert_eq!(revalidated.status(), 200);
+        assert_eq!(revalidated.body(), "reference");
+        assert_eq!(revalidated.headers().get("x-revalidated").unwrap(), "true");
+        assert!(revalidated.cache_status().unwrap().is_hit());
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(server.last_request().header("If-None-Match"), Some("\"v1\""));
+    }
+
+    #[tokio::test]
+    async fn async_disk_cache_test() {
+        let directory = std::env::temp_dir().join(format!("clientix-cache-{}", std::process::id()));
+        let server = TestServer::start(|_| TestResponse::ok("on disk")
+            .header("Cache-Control", "max-age=60")
+            .header("Vary", "Accept-Language"));
+
+        let client = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .cache(DiskCache::new(&directory))
+            .setup();
+
+        assert_eq!(client.get_result_full_response_string().await.unwrap().cache_status(), Some(CacheStatus::Miss));
+
+        // a second client sharing the directory is served from the disk
+        let other = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .cache(DiskCache::new(&directory))
+            .setup();
+
+        let cached = other.get_result_full_response_string().await.unwrap();
+        assert_eq!(cached.cache_status(), Some(CacheStatus::Hit));
+        assert_eq!(cached.body(), "on disk");
+        assert_eq!(server.requests().len(), 1);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        // a response fetched with credentials is neither written to the shared directory nor served to others
+        let authorized = AsyncExampleClient::config()
+            .url(server.url().as_str())
+            .bearer_auth("secret")
+            .cache(DiskCache::new(&directory))
+            .setup();
+
+        assert_eq!(authorized.get_result_full_response_string().await.unwrap().cache_status(), Some(CacheStatus::Miss));
+        assert!(!directory.exists());
+        assert_eq!(other.get_result_full_response_string().await.unwrap().cache_status(), Some(CacheStatus::Miss));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+
+    #[test]
+    fn blocking_idempotency_key_test() {
+        let server = TestServer::echo();
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+
+        client.create_payment(bulk_request()).unwrap();
+        client.create_payment(bulk_request()).unwrap();
+        client.create_payment_with_key("payment-42", bulk_request()).unwrap();
+
+        let keys = server.requests().iter()
+            .map(|request| request.header("Idempotency-Key").unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        // every logical call gets its own random UUID
+        assert_eq!(keys[0].len(), 36);
+        assert_eq!(keys[0].chars().nth(14), Some('4'));
+        assert_ne!(keys[0], keys[1]);
+        assert_eq!(keys[2], "payment-42");
+    }
+
+    #[tokio::test]
+    async fn async_idempotency_key_failover_test() {
+        let unavailable = TestServer::start(|_| TestResponse::status(503, "unavailable"));
+        let server = TestServer::echo();
+
+        let client = AsyncReplicaClient::config()
+            .urls(&[unavailable.url().as_str(), server.url().as_str()])
+            .load_balancing(BalanceStrategy::RoundRobin)
+            .setup();
+
+        // a post with an idempotency key is safe to replay on another replica, with the same key
+        assert_eq!(client.create_payment(None, bulk_request()).await.unwrap(), "ok");
+        let key = unavailable.last_request().header("Idempotency-Key").unwrap().to_string();
+        assert_eq!(server.last_request().header("Idempotency-Key"), Some(key.as_str()));
+
+        client.create_payment(Some("payment-7".to_string()), bulk_request()).await.unwrap();
+        assert_eq!(server.last_request().header("Idempotency-Key"), Some("payment-7"));
+    }
+
+    fn summaries(ids: std::ops::Range<usize>) -> String {
+        let summaries = ids.map(|id| format!("{{\"id\": \"{id}\", \"name\": \"object {id}\"}}")).collect::<Vec<_>>();
+        format!("[{}]", summaries.join(", "))
+    }
+
+    fn query_param<'a>(request: &'a RecordedRequest, name: &str) -> Option<&'a str> {
+        request.query().split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    #[test]
+    fn blocking_link_pagination_test() {
+        let server = TestServer::start(|request| match query_param(request, "page") {
+            None => TestResponse::ok(summaries(1..3).as_str())
+                .header("Link", "</objects/pages?color=red&page=2>; rel=\"next\", </objects/pages?color=red&page=2>; rel=\"last\""),
+            Some(_) => TestResponse::ok(summaries(3..4).as_str())
+        });
+
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+        let mut objects = client.list_objects("red");
+
+        // nothing is requested before the first item is read
+        assert_eq!(server.requests().len(), 0);
+        assert_eq!(objects.next().unwrap().unwrap().id, "1");
+        assert_eq!(server.requests().len(), 1);
+
+        let rest = objects.map(|object| object.unwrap().id).collect::<Vec<_>>();
+        assert_eq!(rest, vec!["2", "3"]);
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(server.requests()[0].query(), "color=red");
+        assert_eq!(server.last_request().target, "/objects/pages?color=red&page=2");
+    }
+
+    #[test]
+    fn blocking_link_pagination_cross_origin_test() {
+        let foreign = TestServer::echo();
+        let next = format!("<{}/objects/pages?page=2>; rel=\"next\"", foreign.url());
+        let server = TestServer::start(move |_| TestResponse::ok(summaries(1..3).as_str()).header("Link", next.as_str()));
+
+        let client = BlockingExampleClient::config()
+            .url(server.url().as_str())
+            .bearer_auth("secret")
+            .setup();
+
+        let mut objects = client.list_objects("red");
+
+        // the items of the first page are kept, the foreign next page is never requested
+        assert_eq!(objects.next().unwrap().unwrap().id, "1");
+        assert_eq!(objects.next().unwrap().unwrap().id, "2");
+        assert!(matches!(objects.next(), Some(Err(ClientixError::InvalidResponse(_, _)))));
+        assert!(objects.next().is_none());
+        assert!(foreign.requests().is_empty());
+    }
+
+    #[test]
+    fn blocking_offset_pagination_test() {
+        let server = TestServer::start(|request| {
+            let offset = query_param(request, "offset").unwrap().parse::<usize>().unwrap();
+            let limit = query_param(request, "limit").unwrap().parse::<usize>().unwrap();
+            let body = format!("{{\"data\": {}}}", summaries(offset..(offset + limit).min(5)));
+
+            TestResponse::ok(body.as_str())
+        });
+
+        let client = BlockingExampleClient::config().url(server.url().as_str()).setup();
+        let objects = client.list_objects_by_offset("archived", "acme")
+            .collect::<ClientixResult<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(objects.len(), 5);
+        assert_eq!(objects[4].name, "object 4");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].target, "/objects/archived?offset=4&limit=2");
+        assert!(requests.iter().all(|request| request.header("X-Tenant") == Some("acme")));
+    }
+
+    #[tokio::test]
+    async fn async_cursor_pagination_test() {
+        let server = TestServer::start(|request| {
+            let (ids, next) = match query_param(request, "cursor") {
+                None => (0..2, "\"c2\""),
+                Some("c2") => (2..4, "\"c3\""),
+                _ => (4..5, "null")
+            };
+
+            TestResponse::ok(format!("{{\"data\": {}, \"meta\": {{\"next\": {next}}}}}", summaries(ids)).as_str())
+        });
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+        let objects = client.list_objects("archived", "red").await.collect().await.unwrap();
+
+        assert_eq!(objects.iter().map(|object| object.id.as_str()).collect::<Vec<_>>(), vec!["0", "1", "2", "3", "4"]);
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(server.last_request().target, "/objects/archived?color=red&cursor=c3");
+    }
+
+    #[tokio::test]
+    async fn async_pagination_error_test() {
+        let server = TestServer::start(|request| match query_param(request, "cursor") {
+            None => TestResponse::ok(format!("{{\"data\": {}, \"meta\": {{\"next\": \"c2\"}}}}", summaries(0..1)).as_str()),
+            Some(_) => TestResponse::status(500, "failed")
+        });
+
+        let client = AsyncExampleClient::config().url(server.url().as_str()).setup();
+        let mut objects = client.list_objects("archived", "red").await;
+
+        // the items already read are kept, the failed page ends the stream
+        assert_eq!(objects.next().await.unwrap().unwrap().id, "0");
+        assert!(matches!(objects.next().await, Some(Err(ClientixError::InvalidResponse(_, _)))));
+        assert!(objects.next().await.is_none());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn openapi_generated_client_test() {
+        let server = TestServer::start(|request| match request.method.as_str() {
+            "POST" => TestResponse::status(201, r#"{"id":2,"name":"lamp","unitPrice":19.5}"#),
+            _ => TestResponse::ok(r#"{"id":1,"name":"desk","currency":"EUR"}"#),
+        });
+
+        let client = CatalogClient::config().url(server.url().as_str()).setup();
+
+        let product = client.get_product(1, Some(Currency::Eur), "acme").await.unwrap().body();
+        assert_eq!(product.name, "desk");
+        assert_eq!(product.currency, Some(Currency::Eur));
+
+        let request = server.last_request();
+        assert_eq!(request.target, "/products/1?currency=EUR");
+        assert_eq!(request.header("X-Tenant"), Some("acme"));
+
+        let created = client.create_product(CreateProductRequest { name: "lamp".to_string(), unit_price: Some(19.5) }).await.unwrap().body();
+        assert_eq!(created.id, 2);
+        assert_eq!(String::from_utf8_lossy(&server.last_request().body), r#"{"name":"lamp","unitPrice":19.5}"#);
+    }
+
+}
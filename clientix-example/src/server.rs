@@ -0,0 +1,422 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa, KeyPair};
+use rustls::pki_types::PrivateKeyDer;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, ServerConnection, StreamOwned};
+
+#[derive(Debug, Clone, Default)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+type Handler = dyn Fn(&RecordedRequest) -> TestResponse + Send + Sync;
+
+pub struct TestServer {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+pub struct TestProxy {
+    url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+pub struct TestCertificate {
+    certificate: Certificate,
+    key: KeyPair,
+}
+
+impl RecordedRequest {
+
+    pub fn query(&self) -> &str {
+        self.target.split_once('?').map(|(_, query)| query).unwrap_or_default()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+}
+
+impl TestResponse {
+
+    pub fn ok(body: &str) -> Self {
+        Self::status(200, body)
+    }
+
+    pub fn status(status: u16, body: &str) -> Self {
+        TestResponse { status, headers: vec![], body: body.as_bytes().to_vec() }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+}
+
+impl TestServer {
+
+    pub fn start<F>(handler: F) -> Self where F: Fn(&RecordedRequest) -> TestResponse + Send + Sync + 'static {
+        Self::listen(None, Arc::new(handler))
+    }
+
+    // serves https with the given certificate, client certificates are required when a client authority is given
+    pub fn start_tls<F>(certificate: &TestCertificate, client_authority: Option<&TestCertificate>, handler: F) -> Self
+    where F: Fn(&RecordedRequest) -> TestResponse + Send + Sync + 'static {
+        Self::start_tls_chain(certificate, &[], client_authority, handler)
+    }
+
+    // the chain certificates are sent after the leaf as they are, valid or not
+    pub fn start_tls_chain<F>(certificate: &TestCertificate, chain: &[&TestCertificate], client_authority: Option<&TestCertificate>, handler: F) -> Self
+    where F: Fn(&RecordedRequest) -> TestResponse + Send + Sync + 'static {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .expect("unsupported tls protocol versions");
+
+        let builder = match client_authority {
+            Some(authority) => {
+                let mut roots = RootCertStore::empty();
+                roots.add(authority.certificate.der().clone()).expect("invalid client authority");
+
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                    .build()
+                    .expect("failed to build client verifier");
+
+                builder.with_client_cert_verifier(verifier)
+            },
+            None => builder.with_no_client_auth()
+        };
+
+        let key = PrivateKeyDer::try_from(certificate.key.serialize_der()).expect("invalid private key");
+        let certificates = std::iter::once(certificate).chain(chain.iter().copied())
+            .map(|certificate| certificate.certificate.der().clone())
+            .collect();
+        let config = builder.with_single_cert(certificates, key)
+            .expect("invalid server certificate");
+
+        Self::listen(Some(Arc::new(config)), Arc::new(handler))
+    }
+
+    #[cfg(unix)]
+    pub fn start_unix<F>(path: &std::path::Path, handler: F) -> Self where F: Fn(&RecordedRequest) -> TestResponse + Send + Sync + 'static {
+        let _ = std::fs::remove_file(path);
+        let listener = std::os::unix::net::UnixListener::bind(path).expect("failed to bind test socket");
+        let url = format!("unix://{}", path.display());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let recorded = recorded.clone();
+                let handler = handler.clone();
+                thread::spawn(move || serve_connection(stream, recorded, handler));
+            }
+        });
+
+        TestServer { url, requests }
+    }
+
+    // speaks HTTP/2 over plain tcp only, clients have to use prior knowledge
+    pub fn start_h2c<F>(handler: F) -> Self where F: Fn(&RecordedRequest) -> TestResponse + Send + Sync + 'static {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind test server");
+        let url = format!("http://{}", listener.local_addr().expect("missing local address"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().expect("failed to build runtime");
+            runtime.block_on(async move {
+                listener.set_nonblocking(true).expect("failed to configure test server");
+                let listener = tokio::net::TcpListener::from_std(listener).expect("failed to register test server");
+
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve_h2c_connection(stream, recorded.clone(), handler.clone()));
+                }
+            });
+        });
+
+        TestServer { url, requests }
+    }
+
+    fn listen(tls: Option<Arc<ServerConfig>>, handler: Arc<Handler>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind test server");
+        let scheme = if tls.is_some() { "https" } else { "http" };
+        let url = format!("{scheme}://{}", listener.local_addr().expect("missing local address"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let recorded = recorded.clone();
+                let handler = handler.clone();
+                let tls = tls.clone();
+                thread::spawn(move || match tls {
+                    Some(tls) => if let Ok(connection) = ServerConnection::new(tls) {
+                        serve_connection(StreamOwned::new(connection, stream), recorded, handler)
+                    },
+                    None => serve_connection(stream, recorded, handler)
+                });
+            }
+        });
+
+        TestServer { url, requests }
+    }
+
+    pub fn echo() -> Self {
+        Self::start(|_| TestResponse::ok("ok"))
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn last_request(&self) -> RecordedRequest {
+        self.requests().pop().expect("no requests received")
+    }
+
+}
+
+impl TestProxy {
+
+    // tunnels CONNECT requests and forwards plain ones, answers 407 without a Proxy-Authorization header when authenticated
+    pub fn start(authenticated: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind test proxy");
+        let url = format!("http://{}", listener.local_addr().expect("missing local address"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let recorded = recorded.clone();
+                thread::spawn(move || proxy_connection(stream, recorded, authenticated));
+            }
+        });
+
+        TestProxy { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+}
+
+impl TestCertificate {
+
+    pub fn authority(name: &str) -> Self {
+        let mut params = CertificateParams::new(Vec::<String>::new()).expect("invalid certificate parameters");
+        params.distinguished_name.push(rcgen::DnType::CommonName, name);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+
+        let key = KeyPair::generate().expect("failed to generate key");
+        let certificate = params.self_signed(&key).expect("failed to sign certificate");
+
+        TestCertificate { certificate, key }
+    }
+
+    pub fn self_signed(name: &str) -> Self {
+        let params = CertificateParams::new(vec![name.to_string()]).expect("invalid certificate parameters");
+        let key = KeyPair::generate().expect("failed to generate key");
+        let certificate = params.self_signed(&key).expect("failed to sign certificate");
+
+        TestCertificate { certificate, key }
+    }
+
+    pub fn issue(&self, name: &str) -> Self {
+        let params = CertificateParams::new(vec![name.to_string()]).expect("invalid certificate parameters");
+        let key = KeyPair::generate().expect("failed to generate key");
+        let certificate = params.signed_by(&key, &self.certificate, &self.key).expect("failed to sign certificate");
+
+        TestCertificate { certificate, key }
+    }
+
+    pub fn certificate_pem(&self) -> String {
+        self.certificate.pem()
+    }
+
+    pub fn key_pem(&self) -> String {
+        self.key.serialize_pem()
+    }
+
+    pub fn der(&self) -> &[u8] {
+        self.certificate.der()
+    }
+
+}
+
+fn serve_connection<S: Read + Write>(stream: S, requests: Arc<Mutex<Vec<RecordedRequest>>>, handler: Arc<Handler>) {
+    let mut reader = BufReader::new(stream);
+
+    while let Some(request) = read_request(&mut reader) {
+        requests.lock().unwrap().push(request.clone());
+
+        let response = handler(&request);
+        if write_response(reader.get_mut(), &request, response).is_err() {
+            return;
+        }
+    }
+}
+
+async fn serve_h2c_connection(stream: tokio::net::TcpStream, requests: Arc<Mutex<Vec<RecordedRequest>>>, handler: Arc<Handler>) {
+    let mut connection = match h2::server::handshake(stream).await {
+        Ok(connection) => connection,
+        Err(_) => return
+    };
+
+    while let Some(Ok((request, mut respond))) = connection.accept().await {
+        let (parts, mut body) = request.into_parts();
+
+        let mut recorded = RecordedRequest {
+            method: parts.method.to_string(),
+            target: parts.uri.path_and_query().map(|target| target.to_string()).unwrap_or_default(),
+            headers: parts.headers.iter()
+                .map(|(name, value)| (name.to_string(), value.to_str().unwrap_or_default().to_string()))
+                .collect(),
+            body: vec![]
+        };
+
+        while let Some(Ok(chunk)) = body.data().await {
+            let _ = body.flow_control().release_capacity(chunk.len());
+            recorded.body.extend_from_slice(&chunk);
+        }
+
+        requests.lock().unwrap().push(recorded.clone());
+
+        let response = handler(&recorded);
+        let mut head = http::Response::builder().status(response.status);
+        for (name, value) in response.headers.iter() {
+            head = head.header(name, value);
+        }
+
+        if let Ok(mut send) = respond.send_response(head.body(()).expect("invalid test response"), false) {
+            let _ = send.send_data(bytes::Bytes::from(response.body), true);
+        }
+    }
+}
+
+fn proxy_connection(mut stream: TcpStream, requests: Arc<Mutex<Vec<RecordedRequest>>>, authenticated: bool) -> Option<()> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let request = read_request(&mut reader)?;
+    requests.lock().unwrap().push(request.clone());
+
+    if authenticated && request.header("proxy-authorization").is_none() {
+        let response = TestResponse::status(407, "").header("Proxy-Authenticate", r#"Basic realm="proxy""#);
+        return write_response(&mut stream, &request, response).ok();
+    }
+
+    let mut upstream = match request.method.as_str() {
+        "CONNECT" => {
+            let upstream = TcpStream::connect(request.target.as_str()).ok()?;
+            stream.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n").ok()?;
+            upstream
+        },
+        _ => {
+            // the origin server accepts absolute-form targets, so the request is passed on unchanged
+            let mut upstream = TcpStream::connect(request.header("host")?).ok()?;
+            let mut head = format!("{} {} HTTP/1.1\r\n", request.method, request.target);
+            for (name, value) in request.headers.iter() {
+                head.push_str(format!("{name}: {value}\r\n").as_str());
+            }
+            head.push_str("\r\n");
+
+            upstream.write_all(head.as_bytes()).ok()?;
+            upstream.write_all(&request.body).ok()?;
+            upstream
+        }
+    };
+
+    let mut downstream = upstream.try_clone().ok()?;
+    thread::spawn(move || {
+        let _ = std::io::copy(&mut downstream, &mut stream);
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+
+    let _ = std::io::copy(&mut reader, &mut upstream);
+    upstream.shutdown(Shutdown::Both).ok()
+}
+
+fn read_request<S: Read>(reader: &mut BufReader<S>) -> Option<RecordedRequest> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+
+    let mut parts = line.split_whitespace();
+    let mut request = RecordedRequest {
+        method: parts.next()?.to_string(),
+        target: parts.next()?.to_string(),
+        ..Default::default()
+    };
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            request.headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let content_length = request.header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+    request.body = body;
+
+    Some(request)
+}
+
+fn write_response<S: Write>(writer: &mut S, request: &RecordedRequest, response: TestResponse) -> std::io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} Test\r\n", response.status);
+    for (name, value) in response.headers.iter() {
+        head.push_str(format!("{name}: {value}\r\n").as_str());
+    }
+
+    if !response.headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-length")) {
+        let content_length = if request.method == "HEAD" { 0 } else { response.body.len() };
+        head.push_str(format!("content-length: {content_length}\r\n").as_str());
+    }
+
+    head.push_str("\r\n");
+
+    writer.write_all(head.as_bytes())?;
+    if request.method != "HEAD" {
+        writer.write_all(&response.body)?;
+    }
+
+    writer.flush()
+}
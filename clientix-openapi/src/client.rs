@@ -0,0 +1,258 @@
+use std::collections::{BTreeSet, HashSet};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use crate::models::{doc, Models};
+use crate::naming::{pascal_case, snake_case};
+use crate::spec::{Operation, Parameter, ParameterLocation, Schema};
+
+const APPLICATION_JSON: &str = "application/json";
+const APPLICATION_XML: &str = "application/xml";
+const APPLICATION_FORM: &str = "application/x-www-form-urlencoded";
+// the bodies #[body] can encode, in order of preference
+const BODY_CONTENT_TYPES: &[&str] = &[APPLICATION_JSON, APPLICATION_FORM, APPLICATION_XML];
+
+pub(crate) struct ClientTrait {
+    pub(crate) tokens: TokenStream,
+    pub(crate) macros: BTreeSet<&'static str>
+}
+
+// one trait method per operation, in the order of the document
+pub(crate) fn compile_client(models: &mut Models, name: &str, asynchronous: bool) -> ClientTrait {
+    let spec = models.spec();
+    let mut macros = BTreeSet::from(["clientix"]);
+    let mut method_names = HashSet::new();
+    let mut methods = vec![];
+
+    for (path, item) in spec.paths.iter() {
+        for (method, operation) in item.operations() {
+            let parameters = item.parameters.iter()
+                .chain(operation.parameters.iter())
+                .filter_map(|parameter| spec.parameter(parameter))
+                .collect::<Vec<_>>();
+
+            // operation parameters override the path ones with the same name and location
+            let parameters = parameters.iter().enumerate()
+                .filter(|(index, parameter)| !parameters[index + 1..].iter().any(|other| other.name == parameter.name && other.location == parameter.location))
+                .map(|(_, parameter)| *parameter)
+                .collect::<Vec<_>>();
+
+            let mut method_name = snake_case(operation.operation_id.clone().unwrap_or_else(|| format!("{method} {path}")).as_str());
+            while !method_names.insert(method_name.clone()) {
+                method_name.push('_');
+            }
+
+            if let Some(compiled) = compile_method(models, method, path, operation, &parameters, method_name.as_str(), asynchronous) {
+                macros.insert(method);
+                methods.push(compiled);
+            }
+        }
+    }
+
+    let spec = models.spec();
+    let mut client_params = vec![];
+    match spec.server_url() {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => client_params.push(quote!(url = #url)),
+        Some(path) if path.starts_with('/') && path != "/" => client_params.push(quote!(path = #path)),
+        _ => ()
+    }
+
+    if asynchronous {
+        client_params.push(quote!(async = true));
+    }
+
+    let ident = format_ident!("{}", name);
+    let description = spec.info.description.clone().unwrap_or_else(|| spec.info.title.clone());
+    let doc = doc(Some(description.as_str()));
+    let tokens = quote! {
+        #doc
+        #[clientix(#(#client_params),*)]
+        pub trait #ident {
+            #(#methods)*
+        }
+    };
+
+    ClientTrait { tokens, macros }
+}
+
+fn compile_method(
+    models: &mut Models,
+    method: &str,
+    path: &str,
+    operation: &Operation,
+    parameters: &[&Parameter],
+    method_name: &str,
+    asynchronous: bool
+) -> Option<TokenStream> {
+    let type_hint = pascal_case(method_name);
+    let mut argument_names = HashSet::new();
+    let mut arguments = vec![];
+    let mut method_params = vec![quote!(path = #path)];
+
+    for parameter in parameters {
+        let mut argument_name = snake_case(parameter.name.as_str());
+        while !argument_names.insert(argument_name.clone()) {
+            argument_name.push('_');
+        }
+
+        let hint = format!("{type_hint}{}", pascal_case(parameter.name.as_str()));
+        arguments.push(compile_argument(models, parameter, argument_name.as_str(), hint.as_str()));
+    }
+
+    let spec = models.spec();
+    if let Some(request_body) = operation.request_body.as_ref().and_then(|request_body| spec.request_body(request_body)) {
+        let content = BODY_CONTENT_TYPES.iter()
+            .find_map(|content_type| request_body.content.get(*content_type).map(|media| (*content_type, media)));
+
+        let (content_type, media) = match content {
+            Some(content) => content,
+            None => {
+                let content_types = request_body.content.keys().cloned().collect::<Vec<_>>().join(", ");
+                models.warn(format!("{method_name} skipped: request body {content_types} is not supported"));
+                return None;
+            }
+        };
+
+        let body_type = match &media.schema {
+            Some(schema) => models.rust_type(schema, format!("{type_hint}Request").as_str()),
+            None => quote!(clientix::prelude::serde_json::Value)
+        };
+
+        let mut argument_name = "body".to_string();
+        while !argument_names.insert(argument_name.clone()) {
+            argument_name.push('_');
+        }
+
+        let argument = format_ident!("{}", argument_name);
+        method_params.push(quote!(consumes = #content_type));
+        arguments.push(quote!(#[body] #argument: #body_type));
+    }
+
+    let (output, produces) = compile_output(models, operation, method_name, type_hint.as_str());
+    if let Some(produces) = produces {
+        method_params.push(quote!(produces = #produces));
+    }
+
+    let documentation = [operation.summary.as_deref(), operation.description.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let doc = doc(Some(documentation.as_str()));
+
+    let method = format_ident!("{}", method);
+    let ident = format_ident!("{}", method_name);
+    let asyncness = match asynchronous {
+        true => quote!(async),
+        false => quote!()
+    };
+
+    Some(quote! {
+        #doc
+        #[#method(#(#method_params),*)]
+        #asyncness fn #ident(&self #(, #arguments)*) -> ClientixResult<ClientixResponse<#output>>;
+    })
+}
+
+fn compile_argument(models: &mut Models, parameter: &Parameter, argument_name: &str, hint: &str) -> TokenStream {
+    let argument = format_ident!("{}", argument_name);
+    let name = parameter.name.as_str();
+    let renamed = argument_name != name;
+    let schema = parameter.schema.clone().unwrap_or_else(|| Schema { schema_type: Some(crate::spec::SchemaType::Single("string".to_string())), ..Default::default() });
+    let resolved = models.resolve(&schema).clone();
+    let ty = models.rust_type(&schema, hint);
+    let string = resolved.types() == ["string"] && !resolved.is_string_enum();
+    let required = parameter.required || parameter.location == ParameterLocation::Path;
+
+    match parameter.location {
+        ParameterLocation::Path => {
+            let ty = if string { quote!(&str) } else { ty };
+            match renamed {
+                true => quote!(#[segment(name = #name)] #argument: #ty),
+                false => quote!(#[segment] #argument: #ty)
+            }
+        },
+        ParameterLocation::Query => {
+            // objects exploded into their properties, the default style of object query params
+            if resolved.is_object() {
+                return quote!(#[query(flatten)] #argument: #ty);
+            }
+
+            let mut query_params = vec![];
+            if renamed {
+                query_params.push(quote!(name = #name));
+            }
+
+            if resolved.types() == ["array"] && parameter.explode == Some(false) {
+                query_params.push(quote!(style = "comma"));
+            }
+
+            let default_value = resolved.default.as_ref().and_then(|value| match value {
+                serde_json::Value::String(value) => Some(value.clone()),
+                serde_json::Value::Number(value) => Some(value.to_string()),
+                serde_json::Value::Bool(value) => Some(value.to_string()),
+                _ => None
+            });
+
+            let ty = match (required, string) {
+                (true, true) => quote!(&str),
+                (true, false) => ty,
+                (false, true) => quote!(Option<&str>),
+                (false, false) => quote!(Option<#ty>)
+            };
+
+            if let (false, Some(default_value)) = (required, default_value) {
+                query_params.push(quote!(default_value = #default_value));
+            }
+
+            match query_params.is_empty() {
+                true => quote!(#[query] #argument: #ty),
+                false => quote!(#[query(#(#query_params),*)] #argument: #ty)
+            }
+        },
+        ParameterLocation::Header | ParameterLocation::Cookie => {
+            let ty = match (required, string) {
+                (true, true) => quote!(&str),
+                (true, false) => ty,
+                (false, _) if string => quote!(Option<String>),
+                (false, _) => quote!(Option<#ty>)
+            };
+
+            match parameter.location {
+                ParameterLocation::Header => quote!(#[header(name = #name)] #argument: #ty),
+                _ if renamed => quote!(#[cookie(name = #name)] #argument: #ty),
+                _ => quote!(#[cookie] #argument: #ty)
+            }
+        }
+    }
+}
+
+// the body of the first 2xx response; responses without one are read as text
+fn compile_output(models: &mut Models, operation: &Operation, method_name: &str, type_hint: &str) -> (TokenStream, Option<&'static str>) {
+    let spec = models.spec();
+    let response = operation.responses.iter()
+        .filter(|(status, _)| status.starts_with('2'))
+        .find_map(|(_, response)| spec.response(response));
+
+    let content = match response {
+        Some(response) if !response.content.is_empty() => &response.content,
+        _ => return (quote!(String), None)
+    };
+
+    for content_type in [APPLICATION_JSON, APPLICATION_XML, APPLICATION_FORM] {
+        if let Some(media) = content.get(content_type) {
+            let output = match &media.schema {
+                Some(schema) => models.rust_type(schema, format!("{type_hint}Response").as_str()),
+                None => quote!(clientix::prelude::serde_json::Value)
+            };
+
+            return (output, Some(content_type));
+        }
+    }
+
+    if !content.keys().any(|content_type| content_type.starts_with("text/")) {
+        let content_types = content.keys().cloned().collect::<Vec<_>>().join(", ");
+        models.warn(format!("{method_name}: response {content_types} is read as text"));
+    }
+
+    (quote!(String), None)
+}
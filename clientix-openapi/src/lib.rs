@@ -0,0 +1,214 @@
+mod client;
+mod models;
+mod naming;
+mod spec;
+
+use std::fs;
+use std::path::Path;
+use quote::{format_ident, quote};
+use thiserror::Error;
+use crate::client::compile_client;
+use crate::models::Models;
+use crate::naming::pascal_case;
+
+pub use spec::*;
+
+const HEADER: &str = "// @generated by clientix-openapi, do not edit by hand\n\n";
+
+pub type OpenApiResult<T> = Result<T, OpenApiError>;
+
+#[derive(Error, Debug)]
+pub enum OpenApiError {
+    #[error("IO error")]
+    IO(#[from] std::io::Error),
+
+    #[error("invalid OpenAPI document: {0}")]
+    Parse(String),
+
+    #[error("unsupported OpenAPI document: {0}")]
+    Unsupported(String),
+
+    #[error("generated code is not valid Rust")]
+    Syntax(#[from] syn::Error),
+}
+
+// Generates a #[clientix] trait and its DTOs from an OpenAPI document, e.g. in a build script:
+//
+// let client = Generator::from_path("openapi.yaml")?.asynchronous(true).generate()?;
+// client.write(Path::new(&env::var("OUT_DIR")?).join("client.rs"))?;
+pub struct Generator {
+    spec: OpenApi,
+    client_name: Option<String>,
+    asynchronous: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneratedClient {
+    source: String,
+    warnings: Vec<String>
+}
+
+impl Generator {
+
+    pub fn new(spec: OpenApi) -> Self {
+        Generator { spec, client_name: None, asynchronous: false }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> OpenApiResult<Self> {
+        Ok(Generator::new(OpenApi::load(path)?))
+    }
+
+    pub fn client_name(mut self, client_name: &str) -> Self {
+        self.client_name = Some(client_name.to_string());
+        self
+    }
+
+    pub fn asynchronous(mut self, asynchronous: bool) -> Self {
+        self.asynchronous = asynchronous;
+        self
+    }
+
+    pub fn generate(&self) -> OpenApiResult<GeneratedClient> {
+        let client_name = match &self.client_name {
+            Some(client_name) => client_name.clone(),
+            None => match pascal_case(self.spec.info.title.as_str()) {
+                name if name.ends_with("Client") => name,
+                name => format!("{name}Client")
+            }
+        };
+
+        let mut models = Models::new(&self.spec);
+        models.define_components();
+        let client = compile_client(&mut models, client_name.as_str(), self.asynchronous);
+
+        let mut macros = client.macros;
+        if models.has_structs() {
+            macros.insert("data_transfer");
+        }
+
+        let macros = macros.iter().map(|name| format_ident!("{}", name));
+        let trait_tokens = client.tokens;
+        let (items, warnings) = models.into_parts();
+
+        let file = syn::parse2::<syn::File>(quote! {
+            use clientix::client::response::{ClientixResponse, ClientixResult};
+            use clientix::{#(#macros),*};
+
+            #trait_tokens
+
+            #(#items)*
+        })?;
+
+        Ok(GeneratedClient { source: format!("{HEADER}{}", prettyplease::unparse(&file)), warnings })
+    }
+
+}
+
+impl GeneratedClient {
+
+    pub fn source(&self) -> &str {
+        self.source.as_str()
+    }
+
+    // operations and schemas the generator could not map exactly, e.g. multipart bodies
+    pub fn warnings(&self) -> &[String] {
+        self.warnings.as_slice()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> OpenApiResult<()> {
+        Ok(fs::write(path, self.source.as_str())?)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::naming::{pascal_case, snake_case};
+    use crate::{Generator, OpenApi, OpenApiError};
+
+    const PETSTORE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/petstore.yaml");
+    const INVENTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/inventory.json");
+
+    #[test]
+    fn petstore_yaml_snapshot_test() {
+        let client = Generator::from_path(PETSTORE).unwrap().generate().unwrap();
+
+        insta::assert_snapshot!(client.source());
+        assert_eq!(client.warnings(), ["upload_photo skipped: request body multipart/form-data is not supported"]);
+    }
+
+    #[test]
+    fn inventory_json_async_snapshot_test() {
+        let client = Generator::from_path(INVENTORY).unwrap()
+            .client_name("InventoryClient")
+            .asynchronous(true)
+            .generate()
+            .unwrap();
+
+        insta::assert_snapshot!(client.source());
+        assert!(client.warnings().is_empty());
+    }
+
+    #[test]
+    fn unsupported_version_test() {
+        let result = OpenApi::parse("swagger: '2.0'\nopenapi: 2.0.0\ninfo:\n  title: legacy\n");
+
+        assert!(matches!(result, Err(OpenApiError::Unsupported(_))));
+    }
+
+    #[test]
+    fn composition_test() {
+        let spec = OpenApi::parse(r#"
+openapi: 3.1.0
+info:
+  title: shapes
+paths: {}
+components:
+  schemas:
+    Circle:
+      type: object
+      properties:
+        radius:
+          type: number
+    Shape:
+      oneOf:
+        - $ref: '#/components/schemas/Circle'
+        - type: string
+    Drawing:
+      type: object
+      properties:
+        size:
+          anyOf:
+            - type: integer
+            - type: string
+        layer:
+          allOf:
+            - $ref: '#/components/schemas/Circle'
+          oneOf:
+            - type: string
+            - type: integer
+"#).unwrap();
+
+        let client = Generator::new(spec).generate().unwrap();
+        let source = client.source();
+
+        assert!(source.contains("#[serde(crate = \"clientix::prelude::serde\", untagged)]\npub enum Shape {\n    Circle(Circle),\n    String(String),\n}"));
+        assert!(source.contains("pub enum DrawingSize {\n    Integer(i64),\n    String(String),\n}"));
+        assert!(source.contains("pub layer: Option<clientix::prelude::serde_json::Value>"));
+        assert_eq!(client.warnings(), ["DrawingLayer: combining allOf, oneOf and anyOf is not supported, mapped to serde_json::Value"]);
+    }
+
+    #[test]
+    fn naming_test() {
+        assert_eq!(snake_case("petId"), "pet_id");
+        assert_eq!(snake_case("X-Request-ID"), "x_request_id");
+        assert_eq!(snake_case("HTTPStatus"), "http_status");
+        assert_eq!(snake_case("type"), "type_");
+        assert_eq!(snake_case("2fa"), "_2fa");
+        assert_eq!(pascal_case("inventory service"), "InventoryService");
+        assert_eq!(pascal_case("pet_status"), "PetStatus");
+        assert_eq!(pascal_case("self"), "SelfValue");
+        assert_eq!(pascal_case("404"), "Value404");
+    }
+
+}
@@ -0,0 +1,74 @@
+use std::process::ExitCode;
+use clientix_openapi::Generator;
+
+const USAGE: &str = "usage: clientix-openapi <spec.yaml|spec.json> [--output <file>] [--client-name <name>] [--async]";
+
+fn main() -> ExitCode {
+    let mut spec = None;
+    let mut output = None;
+    let mut client_name = None;
+    let mut asynchronous = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => output = args.next(),
+            "--client-name" => client_name = args.next(),
+            "--async" => asynchronous = true,
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            },
+            _ if spec.is_none() && !arg.starts_with('-') => spec = Some(arg),
+            _ => {
+                eprintln!("unexpected argument: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(spec) = spec else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let mut generator = match Generator::from_path(spec.as_str()) {
+        Ok(generator) => generator.asynchronous(asynchronous),
+        Err(error) => {
+            eprintln!("{spec}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(client_name) = client_name {
+        generator = generator.client_name(client_name.as_str());
+    }
+
+    let client = match generator.generate() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("{spec}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for warning in client.warnings() {
+        eprintln!("warning: {warning}");
+    }
+
+    let result = match output {
+        Some(output) => client.write(output.as_str()),
+        None => {
+            print!("{}", client.source());
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
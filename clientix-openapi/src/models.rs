@@ -0,0 +1,357 @@
+use std::collections::{HashMap, HashSet};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use crate::naming::{pascal_case, snake_case};
+use crate::spec::{AdditionalProperties, OpenApi, Schema};
+
+// rust types of the schemas of a document: components are named after their keys, inline objects and enums
+// after the place they are declared at, e.g. the status property of Pet becomes PetStatus
+pub(crate) struct Models<'a> {
+    spec: &'a OpenApi,
+    names: HashSet<String>,
+    components: HashMap<String, String>,
+    items: Vec<TokenStream>,
+    structs: bool,
+    warnings: Vec<String>
+}
+
+impl<'a> Models<'a> {
+
+    pub(crate) fn new(spec: &'a OpenApi) -> Self {
+        let mut models = Models { spec, names: HashSet::new(), components: HashMap::new(), items: vec![], structs: false, warnings: vec![] };
+
+        for name in spec.components.schemas.keys() {
+            let rust_name = models.unique_name(pascal_case(name).as_str());
+            models.components.insert(name.clone(), rust_name);
+        }
+
+        models
+    }
+
+    pub(crate) fn define_components(&mut self) {
+        for (name, schema) in self.spec.components.schemas.iter() {
+            let rust_name = self.components[name].clone();
+
+            if schema.is_string_enum() {
+                self.define_enum(rust_name.as_str(), schema);
+            } else if let Some(variants) = schema.variants().filter(|variants| variants.len() > 1) {
+                self.define_union(rust_name.as_str(), schema, variants);
+            } else if schema.is_object() || (!schema.all_of.is_empty() && schema.one_of.is_empty() && schema.any_of.is_empty()) {
+                self.define_struct(rust_name.as_str(), schema);
+            } else {
+                let index = self.reserve();
+                let ident = format_ident!("{}", rust_name);
+                let doc = doc(schema.description.as_deref());
+                let ty = match schema.is_mixed_composition() {
+                    true => {
+                        self.warn_composition(rust_name.as_str());
+                        quote!(clientix::prelude::serde_json::Value)
+                    },
+                    false => self.rust_type(schema, format!("{rust_name}Item").as_str())
+                };
+                self.items[index] = quote! {
+                    #doc
+                    pub type #ident = #ty;
+                };
+            }
+        }
+    }
+
+    pub(crate) fn spec(&self) -> &'a OpenApi {
+        self.spec
+    }
+
+    pub(crate) fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    fn warn_composition(&mut self, name: &str) {
+        self.warnings.push(format!("{name}: combining allOf, oneOf and anyOf is not supported, mapped to serde_json::Value"));
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<TokenStream>, Vec<String>) {
+        (self.items, self.warnings)
+    }
+
+    pub(crate) fn has_structs(&self) -> bool {
+        self.structs
+    }
+
+    // the type of a schema used as a field, argument or body; inline objects and enums are defined under the hint name
+    pub(crate) fn rust_type(&mut self, schema: &Schema, hint: &str) -> TokenStream {
+        if let Some(reference) = &schema.reference {
+            return match self.spec.schema(reference) {
+                Some((name, _)) => {
+                    let ident = format_ident!("{}", self.components[name]);
+                    quote!(#ident)
+                },
+                None => {
+                    self.warnings.push(format!("unresolved reference {reference}, mapped to serde_json::Value"));
+                    quote!(clientix::prelude::serde_json::Value)
+                }
+            };
+        }
+
+        match (schema.all_of.as_slice(), schema.one_of.as_slice(), schema.any_of.as_slice()) {
+            ([single], [], []) | ([], [single], []) | ([], [], [single]) => return self.rust_type(single, hint),
+            ([], [], []) => (),
+            (_, [], []) => {
+                let name = self.unique_name(hint);
+                self.define_struct(name.as_str(), schema);
+                let ident = format_ident!("{}", name);
+                return quote!(#ident);
+            },
+            ([], _, _) if schema.variants().is_some() => {
+                let name = self.unique_name(hint);
+                self.define_union(name.as_str(), schema, schema.variants().unwrap_or_default());
+                let ident = format_ident!("{}", name);
+                return quote!(#ident);
+            },
+            _ => {
+                self.warn_composition(hint);
+                return quote!(clientix::prelude::serde_json::Value);
+            }
+        }
+
+        if schema.is_string_enum() {
+            let name = self.unique_name(hint);
+            self.define_enum(name.as_str(), schema);
+            let ident = format_ident!("{}", name);
+            return quote!(#ident);
+        }
+
+        match schema.types().as_slice() {
+            ["string"] => quote!(String),
+            ["integer"] => match schema.format.as_deref() {
+                Some("int32") => quote!(i32),
+                _ => quote!(i64)
+            },
+            ["number"] => match schema.format.as_deref() {
+                Some("float") => quote!(f32),
+                _ => quote!(f64)
+            },
+            ["boolean"] => quote!(bool),
+            ["array"] => {
+                let item = match &schema.items {
+                    Some(items) => self.rust_type(items, format!("{hint}Item").as_str()),
+                    None => quote!(clientix::prelude::serde_json::Value)
+                };
+
+                quote!(Vec<#item>)
+            },
+            _ if schema.is_object() && !schema.properties.is_empty() => {
+                let name = self.unique_name(hint);
+                self.define_struct(name.as_str(), schema);
+                let ident = format_ident!("{}", name);
+                quote!(#ident)
+            },
+            _ => match &schema.additional_properties {
+                Some(AdditionalProperties::Schema(values)) => {
+                    let value = self.rust_type(values, format!("{hint}Value").as_str());
+                    quote!(std::collections::HashMap<String, #value>)
+                },
+                _ => quote!(clientix::prelude::serde_json::Value)
+            }
+        }
+    }
+
+    // a component schema referenced by name, None for inline schemas
+    pub(crate) fn component_name(&self, schema: &Schema) -> Option<String> {
+        let (name, _) = self.spec.schema(schema.reference.as_deref()?)?;
+        self.components.get(name).cloned()
+    }
+
+    pub(crate) fn resolve<'s>(&'s self, schema: &'s Schema) -> &'s Schema {
+        match schema.reference.as_deref().and_then(|reference| self.spec.schema(reference)) {
+            Some((_, resolved)) => resolved,
+            None => schema
+        }
+    }
+
+    fn define_struct(&mut self, name: &str, schema: &Schema) {
+        let index = self.reserve();
+        self.structs = true;
+
+        // allOf parts are flattened into one struct, the properties of later parts win
+        let mut properties = vec![];
+        let mut required = HashSet::new();
+        self.collect_properties(schema, &mut properties, &mut required, &mut HashSet::new());
+
+        let mut field_names = HashSet::new();
+        let fields = properties.iter()
+            .map(|(property, property_schema)| {
+                let mut field_name = snake_case(property);
+                while !field_names.insert(field_name.clone()) {
+                    field_name.push('_');
+                }
+
+                let field = format_ident!("{}", field_name);
+                let hint = format!("{name}{}", pascal_case(property));
+                let mut ty = self.rust_type(property_schema, hint.as_str());
+
+                // a struct holding itself directly would have an infinite size
+                if self.component_name(property_schema).as_deref() == Some(name) {
+                    ty = quote!(Box<#ty>);
+                }
+
+                let optional = !required.contains(property) || self.resolve(property_schema).is_nullable() || property_schema.is_nullable();
+                let rename = match field_name == *property {
+                    true => quote!(),
+                    false => quote!(#[serde(rename = #property)])
+                };
+
+                let doc = doc(property_schema.description.as_deref());
+                match optional {
+                    true => quote! {
+                        #doc
+                        #rename
+                        #[serde(skip_serializing_if = "Option::is_none")]
+                        pub #field: Option<#ty>
+                    },
+                    false => quote! {
+                        #doc
+                        #rename
+                        pub #field: #ty
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let ident = format_ident!("{}", name);
+        let doc = doc(schema.description.as_deref());
+        self.items[index] = quote! {
+            #doc
+            #[data_transfer]
+            pub struct #ident {
+                #(#fields),*
+            }
+        };
+    }
+
+    fn collect_properties(&self, schema: &Schema, properties: &mut Vec<(String, Schema)>, required: &mut HashSet<String>, visited: &mut HashSet<String>) {
+        if let Some(reference) = &schema.reference {
+            if let Some((name, resolved)) = self.spec.schema(reference) {
+                if visited.insert(name.to_string()) {
+                    self.collect_properties(resolved, properties, required, visited);
+                }
+            }
+
+            return;
+        }
+
+        for part in schema.all_of.iter() {
+            self.collect_properties(part, properties, required, visited);
+        }
+
+        for (property, property_schema) in schema.properties.iter() {
+            match properties.iter_mut().find(|(name, _)| name == property) {
+                Some((_, existing)) => *existing = property_schema.clone(),
+                None => properties.push((property.clone(), property_schema.clone()))
+            }
+        }
+
+        required.extend(schema.required.iter().cloned());
+    }
+
+    // oneOf and anyOf become an untagged enum, serde picks the first variant the value deserializes into
+    fn define_union(&mut self, name: &str, schema: &Schema, variants: &[Schema]) {
+        let index = self.reserve();
+        let ident = format_ident!("{}", name);
+
+        let mut variant_names = HashSet::new();
+        let declarations = variants.iter()
+            .enumerate()
+            .map(|(position, variant)| {
+                let mut variant_name = self.component_name(variant).unwrap_or_else(|| match variant.types().as_slice() {
+                    [variant_type] => pascal_case(variant_type),
+                    _ => format!("Variant{}", position + 1)
+                });
+                while !variant_names.insert(variant_name.clone()) {
+                    variant_name.push('_');
+                }
+
+                let ty = self.rust_type(variant, format!("{name}{variant_name}").as_str());
+                let variant = format_ident!("{}", variant_name);
+                quote!(#variant(#ty))
+            })
+            .collect::<Vec<_>>();
+
+        let doc = doc(schema.description.as_deref());
+        self.items[index] = quote! {
+            #doc
+            #[derive(clientix::prelude::serde::Serialize, clientix::prelude::serde::Deserialize, Debug, Clone)]
+            #[serde(crate = "clientix::prelude::serde", untagged)]
+            pub enum #ident {
+                #(#declarations),*
+            }
+        };
+    }
+
+    fn define_enum(&mut self, name: &str, schema: &Schema) {
+        let index = self.reserve();
+        let ident = format_ident!("{}", name);
+
+        let mut variant_names = HashSet::new();
+        let variants = schema.enumeration.iter()
+            .filter_map(|value| value.as_str())
+            .map(|value| {
+                let mut variant_name = pascal_case(value);
+                while !variant_names.insert(variant_name.clone()) {
+                    variant_name.push('_');
+                }
+
+                (format_ident!("{}", variant_name), value)
+            })
+            .collect::<Vec<_>>();
+
+        let declarations = variants.iter().map(|(variant, value)| quote! {
+            #[serde(rename = #value)]
+            #variant
+        });
+
+        let values = variants.iter().map(|(variant, value)| quote!(#ident::#variant => #value));
+        let doc = doc(schema.description.as_deref());
+
+        self.items[index] = quote! {
+            #doc
+            #[derive(clientix::prelude::serde::Serialize, clientix::prelude::serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #[serde(crate = "clientix::prelude::serde")]
+            pub enum #ident {
+                #(#declarations),*
+            }
+
+            impl std::fmt::Display for #ident {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let value = match self {
+                        #(#values),*
+                    };
+
+                    write!(f, "{}", value)
+                }
+            }
+        };
+    }
+
+    // items keep the order their definition started in, so a struct comes before its inline members
+    fn reserve(&mut self) -> usize {
+        self.items.push(TokenStream::new());
+        self.items.len() - 1
+    }
+
+    fn unique_name(&mut self, hint: &str) -> String {
+        let mut name = hint.to_string();
+        let mut suffix = 2;
+        while !self.names.insert(name.clone()) {
+            name = format!("{hint}{suffix}");
+            suffix += 1;
+        }
+
+        name
+    }
+
+}
+
+pub(crate) fn doc(description: Option<&str>) -> TokenStream {
+    let lines = description.unwrap_or_default().trim().lines().map(|line| format!(" {}", line.trim_end())).collect::<Vec<_>>();
+    quote!(#(#[doc = #lines])*)
+}
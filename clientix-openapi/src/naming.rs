@@ -0,0 +1,69 @@
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "static", "struct",
+    "super", "trait", "true", "try", "type", "unsafe", "use", "where", "while", "yield"
+];
+
+// words of an identifier split on separators and case changes: "petId", "pet_id" and "Pet-ID" give pet, id
+fn words(value: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let chars = value.chars().collect::<Vec<_>>();
+
+    for (index, char) in chars.iter().enumerate() {
+        if !char.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+
+            continue;
+        }
+
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let boundary = char.is_uppercase() && match previous {
+            Some(previous) if previous.is_lowercase() || previous.is_numeric() => true,
+            Some(previous) if previous.is_uppercase() => next.is_some_and(|next| next.is_lowercase()),
+            _ => false
+        };
+
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+
+        current.extend(char.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+pub fn snake_case(value: &str) -> String {
+    let name = words(value).join("_");
+
+    match name.chars().next() {
+        None => "value".to_string(),
+        Some(first) if first.is_numeric() => format!("_{name}"),
+        Some(_) if KEYWORDS.contains(&name.as_str()) => format!("{name}_"),
+        Some(_) => name
+    }
+}
+
+pub fn pascal_case(value: &str) -> String {
+    let name = words(value).iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<String>();
+
+    match name.chars().next() {
+        None => "Value".to_string(),
+        Some(first) if first.is_numeric() => format!("Value{name}"),
+        Some(_) if name == "Self" => "SelfValue".to_string(),
+        Some(_) => name
+    }
+}
@@ -0,0 +1,287 @@
+use std::fs;
+use std::path::Path;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+use crate::{OpenApiError, OpenApiResult};
+
+const SCHEMA_REFERENCE: &str = "#/components/schemas/";
+const PARAMETER_REFERENCE: &str = "#/components/parameters/";
+const REQUEST_BODY_REFERENCE: &str = "#/components/requestBodies/";
+const RESPONSE_REFERENCE: &str = "#/components/responses/";
+
+// the parts of an OpenAPI 3.0/3.1 document the generator reads, everything else is ignored
+#[derive(Clone, Debug, Deserialize)]
+pub struct OpenApi {
+    pub openapi: String,
+    pub info: Info,
+    #[serde(default)]
+    pub servers: Vec<Server>,
+    #[serde(default)]
+    pub paths: IndexMap<String, PathItem>,
+    #[serde(default)]
+    pub components: Components
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Info {
+    pub title: String,
+    pub description: Option<String>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Server {
+    pub url: String,
+    #[serde(default)]
+    pub variables: IndexMap<String, ServerVariable>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ServerVariable {
+    pub default: String
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PathItem {
+    #[serde(default)]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    pub get: Option<Operation>,
+    pub put: Option<Operation>,
+    pub post: Option<Operation>,
+    pub delete: Option<Operation>,
+    pub head: Option<Operation>,
+    pub patch: Option<Operation>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    pub request_body: Option<ReferenceOr<RequestBody>>,
+    #[serde(default)]
+    pub responses: IndexMap<String, ReferenceOr<Response>>,
+    #[serde(default)]
+    pub deprecated: bool
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+    Cookie
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+    #[serde(default)]
+    pub required: bool,
+    pub schema: Option<Schema>,
+    pub style: Option<String>,
+    pub explode: Option<bool>,
+    pub description: Option<String>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RequestBody {
+    #[serde(default)]
+    pub content: IndexMap<String, MediaType>,
+    #[serde(default)]
+    pub required: bool
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Response {
+    #[serde(default)]
+    pub content: IndexMap<String, MediaType>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MediaType {
+    pub schema: Option<Schema>
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Components {
+    #[serde(default)]
+    pub schemas: IndexMap<String, Schema>,
+    #[serde(default)]
+    pub parameters: IndexMap<String, Parameter>,
+    #[serde(default)]
+    pub request_bodies: IndexMap<String, RequestBody>,
+    #[serde(default)]
+    pub responses: IndexMap<String, Response>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ReferenceOr<T> {
+    Reference {
+        #[serde(rename = "$ref")]
+        reference: String
+    },
+    Item(T)
+}
+
+// 3.0 declares a single type with nullable, 3.1 a list of types that may include "null"
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(String),
+    Multiple(Vec<String>)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Allowed(bool),
+    Schema(Box<Schema>)
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    #[serde(rename = "type")]
+    pub schema_type: Option<SchemaType>,
+    pub format: Option<String>,
+    #[serde(default)]
+    pub nullable: bool,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub properties: IndexMap<String, Schema>,
+    #[serde(default)]
+    pub required: Vec<String>,
+    pub items: Option<Box<Schema>>,
+    pub additional_properties: Option<AdditionalProperties>,
+    #[serde(rename = "enum", default)]
+    pub enumeration: Vec<Value>,
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub all_of: Vec<Schema>,
+    #[serde(default)]
+    pub one_of: Vec<Schema>,
+    #[serde(default)]
+    pub any_of: Vec<Schema>
+}
+
+impl OpenApi {
+
+    pub fn load(path: impl AsRef<Path>) -> OpenApiResult<OpenApi> {
+        let content = fs::read_to_string(path.as_ref())?;
+
+        match path.as_ref().extension().and_then(|extension| extension.to_str()) {
+            Some("json") => serde_json::from_str(content.as_str())
+                .map_err(|error| OpenApiError::Parse(error.to_string()))
+                .and_then(OpenApi::supported),
+            _ => OpenApi::parse(content.as_str())
+        }
+    }
+
+    // YAML is a superset of JSON, so documents in either format parse here; load reads .json files with serde_json
+    pub fn parse(content: &str) -> OpenApiResult<OpenApi> {
+        serde_yaml::from_str::<OpenApi>(content)
+            .map_err(|error| OpenApiError::Parse(error.to_string()))
+            .and_then(OpenApi::supported)
+    }
+
+    fn supported(spec: OpenApi) -> OpenApiResult<OpenApi> {
+        match spec.openapi.starts_with("3.") {
+            true => Ok(spec),
+            false => Err(OpenApiError::Unsupported(format!("openapi version {}, only 3.0 and 3.1 are supported", spec.openapi)))
+        }
+    }
+
+    pub fn schema(&self, reference: &str) -> Option<(&str, &Schema)> {
+        let name = reference.strip_prefix(SCHEMA_REFERENCE)?;
+        self.components.schemas.get_key_value(name).map(|(name, schema)| (name.as_str(), schema))
+    }
+
+    pub fn parameter<'a>(&'a self, parameter: &'a ReferenceOr<Parameter>) -> Option<&'a Parameter> {
+        match parameter {
+            ReferenceOr::Reference { reference } => self.components.parameters.get(reference.strip_prefix(PARAMETER_REFERENCE)?),
+            ReferenceOr::Item(parameter) => Some(parameter)
+        }
+    }
+
+    pub fn request_body<'a>(&'a self, request_body: &'a ReferenceOr<RequestBody>) -> Option<&'a RequestBody> {
+        match request_body {
+            ReferenceOr::Reference { reference } => self.components.request_bodies.get(reference.strip_prefix(REQUEST_BODY_REFERENCE)?),
+            ReferenceOr::Item(request_body) => Some(request_body)
+        }
+    }
+
+    pub fn response<'a>(&'a self, response: &'a ReferenceOr<Response>) -> Option<&'a Response> {
+        match response {
+            ReferenceOr::Reference { reference } => self.components.responses.get(reference.strip_prefix(RESPONSE_REFERENCE)?),
+            ReferenceOr::Item(response) => Some(response)
+        }
+    }
+
+    // the first server with its variables set to their defaults
+    pub fn server_url(&self) -> Option<String> {
+        let server = self.servers.first()?;
+
+        Some(server.variables.iter().fold(server.url.clone(), |url, (name, variable)| {
+            url.replace(format!("{{{name}}}").as_str(), variable.default.as_str())
+        }))
+    }
+
+}
+
+impl PathItem {
+
+    pub fn operations(&self) -> Vec<(&'static str, &Operation)> {
+        [("get", &self.get), ("put", &self.put), ("post", &self.post), ("delete", &self.delete), ("head", &self.head), ("patch", &self.patch)]
+            .into_iter()
+            .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+            .collect()
+    }
+
+}
+
+impl Schema {
+
+    pub fn types(&self) -> Vec<&str> {
+        match &self.schema_type {
+            Some(SchemaType::Single(schema_type)) => vec![schema_type.as_str()],
+            Some(SchemaType::Multiple(schema_types)) => schema_types.iter().map(String::as_str).filter(|schema_type| *schema_type != "null").collect(),
+            None => vec![]
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable || matches!(&self.schema_type, Some(SchemaType::Multiple(schema_types)) if schema_types.iter().any(|schema_type| schema_type == "null"))
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.types() == ["object"] || (self.schema_type.is_none() && !self.properties.is_empty())
+    }
+
+    pub fn is_mixed_composition(&self) -> bool {
+        [self.all_of.is_empty(), self.one_of.is_empty(), self.any_of.is_empty()].iter().filter(|empty| !**empty).count() > 1
+    }
+
+    // the alternatives of a oneOf or anyOf schema, None when it has neither or both
+    pub fn variants(&self) -> Option<&[Schema]> {
+        match (self.one_of.as_slice(), self.any_of.as_slice()) {
+            ([], []) | ([_, ..], [_, ..]) => None,
+            (variants, []) | ([], variants) => Some(variants)
+        }
+    }
+
+    pub fn is_string_enum(&self) -> bool {
+        !self.enumeration.is_empty() && self.enumeration.iter().all(|value| value.is_string() || value.is_null())
+    }
+
+}